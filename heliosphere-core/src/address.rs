@@ -1,6 +1,7 @@
 //! Universal address representation
-use crate::error::Error;
-use alloc::string::String;
+use crate::{error::Error, transaction::TransactionId};
+use alloc::{string::String, vec::Vec};
+use alloy_primitives::{keccak256, B256};
 use core::fmt::{Debug, Display};
 use core::str::FromStr;
 use serde::{Deserialize, Serialize};
@@ -17,10 +18,63 @@ impl Address {
         if bytes[0] == 0x41 {
             Ok(Self(bytes))
         } else {
-            Err(Error::InvalidAddress)
+            Err(Error::InvalidAddressPrefix)
         }
     }
 
+    /// Parse EVM address: 20 bytes hex without 0x41 prefix, `0x` is optional
+    pub fn from_evm_hex(s: &str) -> Result<Self, Error> {
+        let s = strip_0x(s).unwrap_or(s);
+        let bytes: [u8; 20] = decode_hex(s)?
+            .try_into()
+            .map_err(|_| Error::InvalidAddressLength)?;
+        Ok(alloy_primitives::Address::from(bytes).into())
+    }
+
+    /// Parse address from event logs and contract call results.
+    ///
+    /// Accepts everything [`FromStr`] accepts plus 20 bytes hex without `0x`
+    /// (TVM log format) and 32 bytes ABI encoded words (indexed topics).
+    pub fn parse_lenient(s: &str) -> Result<Self, Error> {
+        let strict = s.parse();
+        if strict.is_ok() {
+            return strict;
+        }
+        let hex = strip_0x(s).unwrap_or(s);
+        match decode_hex(hex) {
+            Ok(bytes) if bytes.len() == 20 => Self::from_evm_hex(hex),
+            Ok(bytes) if bytes.len() == 32 && bytes[..12].iter().all(|b| *b == 0) => {
+                Self::from_evm_hex(&hex[24..])
+            }
+            _ => strict,
+        }
+    }
+
+    /// Address of contract created by `CreateSmartContract` transaction:
+    /// last 20 bytes of keccak256 over transaction id and owner address
+    pub fn contract_address(tx_id: &TransactionId, owner: &Address) -> Self {
+        let mut data = [0; 32 + 21];
+        data[..32].copy_from_slice(&tx_id.0);
+        data[32..].copy_from_slice(&owner.0);
+        Self::from_hash(keccak256(data))
+    }
+
+    /// Address of contract created by TVM `CREATE2` opcode.
+    /// Same as in EVM, but hashed data starts with 0x41 instead of 0xff.
+    pub fn create2(deployer: &Address, salt: [u8; 32], init_code: &[u8]) -> Self {
+        let mut data = [0; 21 + 32 + 32];
+        data[..21].copy_from_slice(&deployer.0);
+        data[21..53].copy_from_slice(&salt);
+        data[53..].copy_from_slice(keccak256(init_code).as_slice());
+        Self::from_hash(keccak256(data))
+    }
+
+    fn from_hash(hash: B256) -> Self {
+        let mut bytes = [0x41; 21];
+        bytes[1..].copy_from_slice(&hash[12..]);
+        Self(bytes)
+    }
+
     /// Get base58 representation
     pub fn as_base58(&self) -> alloc::string::String {
         bs58::encode(&self.0).with_check().into_string()
@@ -31,23 +85,55 @@ impl Address {
         hex::encode(self.0)
     }
 
+    /// Get EVM representation: `0x` and 20 bytes hex without 0x41 prefix
+    pub fn to_evm_hex(&self) -> alloc::string::String {
+        alloc::format!("0x{}", hex::encode(&self.0[1..]))
+    }
+
     /// Get raw address bytes (including 0x41 prefix)
     pub fn as_bytes(&self) -> &[u8] {
         &self.0
     }
 }
 
-/// Parse address from base58 or hex string
+fn strip_0x(s: &str) -> Option<&str> {
+    s.strip_prefix("0x").or_else(|| s.strip_prefix("0X"))
+}
+
+fn decode_hex(s: &str) -> Result<Vec<u8>, Error> {
+    hex::decode(s).map_err(|e| match e {
+        hex::FromHexError::OddLength => Error::InvalidAddressLength,
+        _ => Error::InvalidAddressCharacter,
+    })
+}
+
+fn decode_base58(s: &str) -> Result<Vec<u8>, Error> {
+    bs58::decode(s)
+        .with_check(None)
+        .into_vec()
+        .map_err(|e| match e {
+            bs58::decode::Error::InvalidChecksum { .. } => Error::InvalidAddressChecksum,
+            bs58::decode::Error::NoChecksum => Error::InvalidAddressLength,
+            _ => Error::InvalidAddressCharacter,
+        })
+}
+
+/// Parse address from string:
+/// - base58check (`T...`)
+/// - hex with 0x41 prefix, `0x` is optional
+/// - EVM hex (`0x` and 20 bytes)
 impl FromStr for Address {
     type Err = Error;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let bytes = bs58::decode(s)
-            .with_check(None)
-            .into_vec()
-            .or_else(|_| hex::decode(s))
-            .map_err(|_| Error::InvalidAddress)?;
-        Ok(Self(bytes.try_into().map_err(|_| Error::InvalidAddress)?))
+        // base58 addresses are 34 characters long and start with `T`, hex ones are 42 (or 40 for EVM)
+        let bytes = match strip_0x(s) {
+            Some(hex) if hex.len() == 40 => return Self::from_evm_hex(hex),
+            Some(hex) => decode_hex(hex)?,
+            None if s.len() != 34 && s.bytes().all(|b| b.is_ascii_hexdigit()) => decode_hex(s)?,
+            None => decode_base58(s)?,
+        };
+        Self::new(bytes.try_into().map_err(|_| Error::InvalidAddressLength)?)
     }
 }
 
@@ -130,4 +216,161 @@ mod test {
         assert_eq!(a1.as_base58(), b58, "b58 mismatch");
         assert_eq!(a1.as_hex().to_ascii_uppercase(), hex, "hex mismatch");
     }
+
+    #[test]
+    fn test_address_errors() {
+        let b58 = "TNPeeaaFB7K9cmo4uQpcU32zGK8G1NYqeL";
+        let hex = "418840e6c55b9ada326d211d818c34a994aeced808";
+        let cases = [
+            (
+                "TNPeeaaFB7K9cmo4uQpcU32zGK8G1NYqeM",
+                Error::InvalidAddressChecksum,
+            ),
+            (
+                "TNPeeaaFB7K9cmo4uQpcU32zGK8G1NYqe0",
+                Error::InvalidAddressCharacter,
+            ),
+            (
+                "TNPeeaaFB7K9cmo4uQpcU32zGK8G1NY",
+                Error::InvalidAddressChecksum,
+            ),
+            // 0x42 prefix
+            (
+                "428840e6c55b9ada326d211d818c34a994aeced808",
+                Error::InvalidAddressPrefix,
+            ),
+            (
+                "418840e6c55b9ada326d211d818c34a994aeced8",
+                Error::InvalidAddressLength,
+            ),
+            (
+                "418840e6c55b9ada326d211d818c34a994aeced80",
+                Error::InvalidAddressLength,
+            ),
+            (
+                "0x418840e6c55b9ada326d211d818c34a994aeced8zz",
+                Error::InvalidAddressCharacter,
+            ),
+            ("", Error::InvalidAddressLength),
+        ];
+        for (s, err) in cases {
+            let res = s.parse::<Address>();
+            assert_eq!(
+                alloc::format!("{:?}", res),
+                alloc::format!("{:?}", Err::<Address, _>(err)),
+                "{}",
+                s
+            );
+        }
+        let short = bs58::encode(&[0x41; 20]).with_check().into_string();
+        assert!(matches!(
+            short.parse::<Address>(),
+            Err(Error::InvalidAddressLength)
+        ));
+        assert!(Address::new([0; 21]).is_err());
+        assert_eq!(b58.parse::<Address>().unwrap().as_hex(), hex);
+        assert_eq!(
+            alloc::format!("0x{}", hex)
+                .parse::<Address>()
+                .unwrap()
+                .as_base58(),
+            b58
+        );
+    }
+
+    #[test]
+    fn test_address_evm() {
+        let b58 = "TNPeeaaFB7K9cmo4uQpcU32zGK8G1NYqeL";
+        let evm = "0x8840e6c55b9ada326d211d818c34a994aeced808";
+        let address: Address = b58.parse().unwrap();
+        assert_eq!(address.to_evm_hex(), evm);
+        assert_eq!(Address::from_evm_hex(evm).unwrap(), address);
+        assert_eq!(Address::from_evm_hex(&evm[2..]).unwrap(), address);
+        assert_eq!(evm.parse::<Address>().unwrap(), address);
+        assert!(Address::from_evm_hex(&address.as_hex()).is_err());
+        // bare 20 bytes hex is only accepted by lenient parser
+        assert!(evm[2..].parse::<Address>().is_err());
+
+        let topic = "0000000000000000000000008840e6c55b9ada326d211d818c34a994aeced808";
+        for s in [b58, evm, &evm[2..], topic, &address.as_hex()] {
+            assert_eq!(Address::parse_lenient(s).unwrap(), address, "{}", s);
+        }
+        assert!(Address::parse_lenient(
+            &topic.replace("00000000000000000000", "00000000000000000001")
+        )
+        .is_err());
+        assert!(matches!(
+            Address::parse_lenient("TNPeeaaFB7K9cmo4uQpcU32zGK8G1NYqeM"),
+            Err(Error::InvalidAddressChecksum)
+        ));
+    }
+
+    #[test]
+    fn test_contract_address() {
+        // Not an on-chain deployment: reference computed independently with
+        // OpenSSL keccak-256 over txid ++ owner
+        // (`openssl dgst -keccak-256` of 8d9f..e7e7 ++ 418840..d808, last 20 bytes)
+        let owner: Address = "TNPeeaaFB7K9cmo4uQpcU32zGK8G1NYqeL".parse().unwrap();
+        let tx_id = TransactionId(hex_literal::hex!(
+            "8d9fa8690be0cd307c56cc64606dcd404cc9d2fa1855b7a01ffc9eb57f27e7e7"
+        ));
+        assert_eq!(
+            Address::contract_address(&tx_id, &owner).as_base58(),
+            "TAs9t4kcjUd8vJ9g636f7At6TgkKPQpiBx"
+        );
+
+        // EIP-1014 examples: TVM hashes the 0x41 prefixed deployer, EVM 0xff ++ deployer,
+        // so EVM addresses are reproduced with 0xff in place of the address prefix
+        let cases = [
+            (
+                "0000000000000000000000000000000000000000",
+                "0000000000000000000000000000000000000000000000000000000000000000",
+                "00",
+                "4d1a2e2bb4f88f0250f26ffff098b0b30b26bf38",
+            ),
+            (
+                "deadbeef00000000000000000000000000000000",
+                "0000000000000000000000000000000000000000000000000000000000000000",
+                "00",
+                "b928f69bb1d91cd65274e3c79d8986362984fda3",
+            ),
+            (
+                "deadbeef00000000000000000000000000000000",
+                "000000000000000000000000feed000000000000000000000000000000000000",
+                "00",
+                "d04116cdd17bebe565eb2422f2497e06cc1c9833",
+            ),
+            (
+                "0000000000000000000000000000000000000000",
+                "0000000000000000000000000000000000000000000000000000000000000000",
+                "deadbeef",
+                "70f2b2914a2a4b783faefb75f459a580616fcb5e",
+            ),
+            (
+                "00000000000000000000000000000000deadbeef",
+                "00000000000000000000000000000000000000000000000000000000cafebabe",
+                "deadbeef",
+                "60f3f640a8508fc6a86d45df051962668e1e8ac7",
+            ),
+            (
+                "0000000000000000000000000000000000000000",
+                "0000000000000000000000000000000000000000000000000000000000000000",
+                "",
+                "e33c0c7f7df4809055c3eba6c09cfe4baf1bd9e0",
+            ),
+        ];
+        for (deployer, salt, init_code, expected) in cases {
+            let mut evm_deployer = [0xff; 21];
+            hex::decode_to_slice(deployer, &mut evm_deployer[1..]).unwrap();
+            let mut salt_bytes = [0; 32];
+            hex::decode_to_slice(salt, &mut salt_bytes).unwrap();
+            let address = Address::create2(
+                &Address(evm_deployer),
+                salt_bytes,
+                &hex::decode(init_code).unwrap(),
+            );
+            assert_eq!(address.as_bytes()[0], 0x41);
+            assert_eq!(&address.as_hex()[2..], expected);
+        }
+    }
 }
@@ -0,0 +1,375 @@
+//! TRX and token amounts
+use alloc::{
+    format,
+    string::{String, ToString},
+};
+use core::{cmp::Ordering, fmt::Display, str::FromStr};
+
+pub use alloy_primitives::U256;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::Error;
+
+/// SUN in one TRX
+pub const SUN_PER_TRX: u64 = 1_000_000;
+
+/// TRX decimals
+pub const TRX_DECIMALS: u8 = 6;
+
+/// Largest supported number of decimals (10^77 is the largest power of ten below 2^256)
+const MAX_DECIMALS: u8 = 77;
+
+/// Parse unsigned decimal string (e.g. `12.5`) into base units with `decimals` fractional digits
+fn parse_decimal(s: &str, decimals: u8) -> Result<U256, Error> {
+    if decimals > MAX_DECIMALS {
+        return Err(Error::InvalidAmount);
+    }
+    let (int, frac) = s.split_once('.').unwrap_or((s, ""));
+    if (int.is_empty() && frac.is_empty())
+        || frac.len() > decimals as usize
+        || !int.chars().chain(frac.chars()).all(|c| c.is_ascii_digit())
+    {
+        return Err(Error::InvalidAmount);
+    }
+    let digits = |s: &str| {
+        if s.is_empty() {
+            Ok(U256::ZERO)
+        } else {
+            U256::from_str_radix(s, 10).map_err(|_| Error::InvalidAmount)
+        }
+    };
+    let scale = |n: usize| U256::from(10).pow(U256::from(n));
+    // fraction is below 10^decimals after scaling, only integer part can overflow
+    let frac_units = digits(frac)? * scale(decimals as usize - frac.len());
+    digits(int)?
+        .checked_mul(scale(decimals as usize))
+        .and_then(|int_units| int_units.checked_add(frac_units))
+        .ok_or(Error::InvalidAmount)
+}
+
+/// Format base units as decimal string without trailing zeros
+fn format_decimal(value: U256, decimals: u8) -> String {
+    let digits = value.to_string();
+    let decimals = decimals as usize;
+    if decimals == 0 {
+        return digits;
+    }
+    let digits = format!("{:0>width$}", digits, width = decimals + 1);
+    let (int, frac) = digits.split_at(digits.len() - decimals);
+    match frac.trim_end_matches('0') {
+        "" => int.to_string(),
+        frac => format!("{}.{}", int, frac),
+    }
+}
+
+/// TRX amount in SUN (1 TRX = 1,000,000 SUN)
+///
+/// Parsed from and displayed with explicit unit (`"12.5 TRX"`, `"100 SUN"`), serialized as integer SUN.
+/// Arithmetic is checked, there are no operators which could silently overflow.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Sun(u64);
+
+impl Sun {
+    /// Zero amount
+    pub const ZERO: Sun = Sun(0);
+    /// Largest amount
+    pub const MAX: Sun = Sun(u64::MAX);
+
+    /// Amount in SUN
+    pub const fn new(sun: u64) -> Self {
+        Sun(sun)
+    }
+
+    /// Amount in whole TRX, `None` on overflow
+    pub const fn from_trx(trx: u64) -> Option<Self> {
+        match trx.checked_mul(SUN_PER_TRX) {
+            Some(sun) => Some(Sun(sun)),
+            None => None,
+        }
+    }
+
+    /// Parse TRX decimal string without unit (e.g. `12.5`), at most 6 decimals
+    pub fn from_trx_str(trx: &str) -> Result<Self, Error> {
+        parse_decimal(trx, TRX_DECIMALS)?
+            .try_into()
+            .map(Sun)
+            .map_err(|_| Error::InvalidAmount)
+    }
+
+    /// Amount in SUN
+    pub const fn as_sun(&self) -> u64 {
+        self.0
+    }
+
+    /// TRX decimal string without unit (e.g. `12.5`)
+    pub fn to_trx_string(&self) -> String {
+        format_decimal(U256::from(self.0), TRX_DECIMALS)
+    }
+
+    /// Checked addition
+    pub const fn checked_add(self, rhs: Sun) -> Option<Sun> {
+        match self.0.checked_add(rhs.0) {
+            Some(sun) => Some(Sun(sun)),
+            None => None,
+        }
+    }
+
+    /// Checked subtraction
+    pub const fn checked_sub(self, rhs: Sun) -> Option<Sun> {
+        match self.0.checked_sub(rhs.0) {
+            Some(sun) => Some(Sun(sun)),
+            None => None,
+        }
+    }
+
+    /// Checked multiplication by scalar
+    pub const fn checked_mul(self, rhs: u64) -> Option<Sun> {
+        match self.0.checked_mul(rhs) {
+            Some(sun) => Some(Sun(sun)),
+            None => None,
+        }
+    }
+
+    /// Checked division by scalar
+    pub const fn checked_div(self, rhs: u64) -> Option<Sun> {
+        match self.0.checked_div(rhs) {
+            Some(sun) => Some(Sun(sun)),
+            None => None,
+        }
+    }
+
+    /// Saturating addition
+    pub const fn saturating_add(self, rhs: Sun) -> Sun {
+        Sun(self.0.saturating_add(rhs.0))
+    }
+
+    /// Saturating subtraction
+    pub const fn saturating_sub(self, rhs: Sun) -> Sun {
+        Sun(self.0.saturating_sub(rhs.0))
+    }
+}
+
+impl Display for Sun {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} TRX", self.to_trx_string())
+    }
+}
+
+/// Parse amount with unit: `12.5 TRX`, `12.5TRX` or `12500000 SUN` (unit is case insensitive)
+impl FromStr for Sun {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let split = s
+            .find(|c: char| c.is_ascii_alphabetic())
+            .ok_or(Error::InvalidAmount)?;
+        let (value, unit) = s.split_at(split);
+        let value = value.trim_end();
+        if unit.eq_ignore_ascii_case("TRX") {
+            Sun::from_trx_str(value)
+        } else if unit.eq_ignore_ascii_case("SUN") {
+            value.parse().map(Sun).map_err(|_| Error::InvalidAmount)
+        } else {
+            Err(Error::InvalidAmount)
+        }
+    }
+}
+
+impl Serialize for Sun {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for Sun {
+    /// Integer SUN or string with unit
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            Sun(u64),
+            Str(String),
+        }
+        match Repr::deserialize(deserializer)? {
+            Repr::Sun(sun) => Ok(Sun(sun)),
+            Repr::Str(s) => s.parse().map_err(serde::de::Error::custom),
+        }
+    }
+}
+
+/// Token amount in base units with number of decimals (e.g. TRC20 `balanceOf` result and `decimals()`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TokenAmount {
+    value: U256,
+    decimals: u8,
+}
+
+impl TokenAmount {
+    /// Amount in base units
+    pub fn new(value: U256, decimals: u8) -> Self {
+        Self { value, decimals }
+    }
+
+    /// Zero amount
+    pub fn zero(decimals: u8) -> Self {
+        Self::new(U256::ZERO, decimals)
+    }
+
+    /// Parse decimal string (e.g. `12.5`), fails if it has more than `decimals` fractional digits
+    pub fn parse(s: &str, decimals: u8) -> Result<Self, Error> {
+        Ok(Self::new(parse_decimal(s.trim(), decimals)?, decimals))
+    }
+
+    /// Amount in base units
+    pub fn value(&self) -> U256 {
+        self.value
+    }
+
+    /// Number of decimals
+    pub fn decimals(&self) -> u8 {
+        self.decimals
+    }
+
+    /// Checked addition, `None` on overflow or decimals mismatch
+    pub fn checked_add(self, rhs: TokenAmount) -> Option<TokenAmount> {
+        (self.decimals == rhs.decimals)
+            .then(|| self.value.checked_add(rhs.value))
+            .flatten()
+            .map(|value| Self::new(value, self.decimals))
+    }
+
+    /// Checked subtraction, `None` on underflow or decimals mismatch
+    pub fn checked_sub(self, rhs: TokenAmount) -> Option<TokenAmount> {
+        (self.decimals == rhs.decimals)
+            .then(|| self.value.checked_sub(rhs.value))
+            .flatten()
+            .map(|value| Self::new(value, self.decimals))
+    }
+
+    /// Checked multiplication by scalar
+    pub fn checked_mul(self, rhs: U256) -> Option<TokenAmount> {
+        self.value
+            .checked_mul(rhs)
+            .map(|value| Self::new(value, self.decimals))
+    }
+}
+
+/// Amounts with different decimals are not comparable
+impl PartialOrd for TokenAmount {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        (self.decimals == other.decimals).then(|| self.value.cmp(&other.value))
+    }
+}
+
+/// Decimal string without unit (e.g. `12.5`)
+impl Display for TokenAmount {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", format_decimal(self.value, self.decimals))
+    }
+}
+
+/// Serialized as `{"value": "<base units>", "decimals": <decimals>}`
+impl Serialize for TokenAmount {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        TokenAmountRepr {
+            value: self.value.to_string(),
+            decimals: self.decimals,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for TokenAmount {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = TokenAmountRepr::deserialize(deserializer)?;
+        let value = U256::from_str_radix(&repr.value, 10).map_err(serde::de::Error::custom)?;
+        Ok(Self::new(value, repr.decimals))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct TokenAmountRepr {
+    value: String,
+    decimals: u8,
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::string::ToString;
+
+    use super::*;
+
+    #[test]
+    fn test_sun() {
+        assert_eq!("12.5 TRX".parse::<Sun>().unwrap(), Sun::new(12_500_000));
+        assert_eq!("12.5trx".parse::<Sun>().unwrap(), Sun::new(12_500_000));
+        assert_eq!(" .000001 TRX ".parse::<Sun>().unwrap(), Sun::new(1));
+        assert_eq!("100 SUN".parse::<Sun>().unwrap(), Sun::new(100));
+        for invalid in [
+            "12.5",
+            "100",
+            "0.0000001 TRX",
+            "1.5 SUN",
+            "-1 TRX",
+            "1e6 SUN",
+            ". TRX",
+            "1 USDT",
+            "18446744073710 TRX",
+        ] {
+            assert!(invalid.parse::<Sun>().is_err(), "{}", invalid);
+        }
+        assert_eq!(Sun::new(12_500_000).to_string(), "12.5 TRX");
+        assert_eq!(Sun::new(1).to_string(), "0.000001 TRX");
+        assert_eq!(Sun::from_trx(3).unwrap().to_string(), "3 TRX");
+        assert_eq!(Sun::ZERO.to_string(), "0 TRX");
+        assert_eq!(Sun::from_trx(u64::MAX), None);
+
+        assert_eq!(Sun::new(1).checked_add(Sun::MAX), None);
+        assert_eq!(Sun::new(1).checked_sub(Sun::new(2)), None);
+        assert_eq!(Sun::new(1).saturating_sub(Sun::new(2)), Sun::ZERO);
+        assert_eq!(Sun::new(3).checked_mul(2), Some(Sun::new(6)));
+        assert_eq!(Sun::new(3).checked_div(0), None);
+
+        assert_eq!(serde_json::to_string(&Sun::new(5)).unwrap(), "5");
+        assert_eq!(serde_json::from_str::<Sun>("5").unwrap(), Sun::new(5));
+        assert_eq!(
+            serde_json::from_str::<Sun>("\"1 TRX\"").unwrap(),
+            Sun::new(1_000_000)
+        );
+        assert!(serde_json::from_str::<Sun>("-5").is_err());
+    }
+
+    #[test]
+    fn test_token_amount() {
+        let amount = TokenAmount::parse("1234.5", 18).unwrap();
+        assert_eq!(
+            amount.value(),
+            U256::from(12345u64) * U256::from(10u64).pow(U256::from(17))
+        );
+        assert_eq!(amount.to_string(), "1234.5");
+        assert_eq!(TokenAmount::new(U256::from(5), 0).to_string(), "5");
+        assert_eq!(TokenAmount::new(U256::from(5), 2).to_string(), "0.05");
+        assert_eq!(TokenAmount::new(U256::MAX, 77).to_string().len(), 79);
+        assert!(TokenAmount::parse("0.001", 2).is_err());
+        assert!(TokenAmount::parse("1", 78).is_err());
+        assert!(TokenAmount::parse(&U256::MAX.to_string(), 1).is_err());
+
+        let usdt = |s| TokenAmount::parse(s, 6).unwrap();
+        assert_eq!(usdt("1.5").checked_add(usdt("2")), Some(usdt("3.5")));
+        assert_eq!(usdt("1.5").checked_sub(usdt("2")), None);
+        assert_eq!(
+            usdt("1").checked_add(TokenAmount::parse("1", 18).unwrap()),
+            None
+        );
+        assert!(usdt("1") < usdt("1.000001"));
+        assert_eq!(usdt("1").partial_cmp(&TokenAmount::zero(18)), None);
+
+        let json = serde_json::to_string(&usdt("1.5")).unwrap();
+        assert_eq!(json, r#"{"value":"1500000","decimals":6}"#);
+        assert_eq!(
+            serde_json::from_str::<TokenAmount>(&json).unwrap(),
+            usdt("1.5")
+        );
+    }
+}
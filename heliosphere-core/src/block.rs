@@ -19,6 +19,23 @@ use serde::{Deserialize, Serialize};
 #[repr(transparent)]
 pub struct BlockId(#[serde(with = "as_hex_array")] pub [u8; 32]);
 
+impl BlockId {
+    /// Block number (encoded in first 8 bytes of block id)
+    pub fn block_number(&self) -> u64 {
+        u64::from_be_bytes(self.0[..8].try_into().expect("8 bytes"))
+    }
+
+    /// `ref_block_bytes` of transactions referencing this block (bytes 6..8 of block number)
+    pub fn ref_block_bytes(&self) -> [u8; 2] {
+        [self.0[6], self.0[7]]
+    }
+
+    /// `ref_block_hash` of transactions referencing this block (bytes 8..16 of block id)
+    pub fn ref_block_hash(&self) -> [u8; 8] {
+        self.0[8..16].try_into().expect("8 bytes")
+    }
+}
+
 impl FromStr for BlockId {
     type Err = Error;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
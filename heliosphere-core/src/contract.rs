@@ -0,0 +1,1225 @@
+//! Typed contract (transaction payload) definitions, see java-tron `core/contract/*.proto`
+use crate::{
+    protobuf::{self, FieldValue, Message, Reader, Writer},
+    Address, Error,
+};
+use alloc::{
+    collections::BTreeMap,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+/// Json key of a message field: field name or explicit override
+macro_rules! json_key {
+    ($field:ident) => {
+        stringify!($field)
+    };
+    ($field:ident, $json:literal) => {
+        $json
+    };
+}
+
+/// Declare protobuf message struct (fields in field number order)
+macro_rules! message {
+    (
+        $(#[$meta:meta])*
+        pub struct $name:ident {
+            $(
+                $(#[$fmeta:meta])*
+                pub $field:ident: $ty:ty = $num:literal $(=> $json:literal)?,
+            )*
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub struct $name {
+            $(
+                $(#[$fmeta])*
+                pub $field: $ty,
+            )*
+        }
+
+        impl Message for $name {
+            #[allow(unused_mut)]
+            fn encode(&self) -> Vec<u8> {
+                let mut w = Writer::new();
+                $(protobuf::Field::encode(&self.$field, $num, &mut w);)*
+                w.into_bytes()
+            }
+
+            fn decode(bytes: &[u8]) -> Result<Self, Error> {
+                $(let mut $field: Option<$ty> = None;)*
+                for field in Reader::new(bytes) {
+                    match field? {
+                        $(($num, value) => protobuf::Field::merge(&mut $field, value)?,)*
+                        _ => {}
+                    }
+                }
+                Ok(Self {
+                    $($field: match $field {
+                        Some(x) => x,
+                        None => protobuf::Field::missing()?,
+                    },)*
+                })
+            }
+
+            #[allow(unused_mut)]
+            fn to_json(&self) -> Value {
+                let mut map = Map::new();
+                $(if let Some(value) = protobuf::Field::to_json(&self.$field) {
+                    map.insert(json_key!($field $(, $json)?).into(), value);
+                })*
+                Value::Object(map)
+            }
+
+            #[allow(unused_variables)]
+            fn from_json(value: &Value) -> Result<Self, Error> {
+                let map = value.as_object().ok_or(Error::InvalidContract)?;
+                Ok(Self {
+                    $($field: match map.get(json_key!($field $(, $json)?)) {
+                        Some(value) if !value.is_null() => protobuf::Field::from_json(value)?,
+                        _ => protobuf::Field::missing()?,
+                    },)*
+                })
+            }
+        }
+
+        impl protobuf::Field for $name {
+            fn encode(&self, field: u32, w: &mut Writer) {
+                w.message(field, &Message::encode(self));
+            }
+
+            fn merge(slot: &mut Option<Self>, value: FieldValue<'_>) -> Result<(), Error> {
+                *slot = Some(Message::decode(value.as_bytes()?)?);
+                Ok(())
+            }
+
+            fn missing() -> Result<Self, Error> {
+                Err(Error::InvalidContract)
+            }
+
+            fn to_json(&self) -> Option<Value> {
+                Some(Message::to_json(self))
+            }
+
+            fn from_json(value: &Value) -> Result<Self, Error> {
+                Message::from_json(value)
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: serde::Serializer,
+            {
+                Message::to_json(self).serialize(serializer)
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                let value = Value::deserialize(deserializer)?;
+                Message::from_json(&value).map_err(serde::de::Error::custom)
+            }
+        }
+    };
+}
+
+/// Declare protobuf enum (first variant is the default one)
+macro_rules! proto_enum {
+    (
+        $(#[$meta:meta])*
+        pub enum $name:ident {
+            $(
+                $(#[$vmeta:meta])*
+                $variant:ident = $num:literal => $json:literal,
+            )*
+        }
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        pub enum $name {
+            $(
+                $(#[$vmeta])*
+                $variant = $num,
+            )*
+        }
+
+        impl $name {
+            /// Name as used in java-tron json
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    $(Self::$variant => $json,)*
+                }
+            }
+
+            fn from_i64(value: i64) -> Result<Self, Error> {
+                match value {
+                    $($num => Ok(Self::$variant),)*
+                    _ => Err(Error::InvalidContract),
+                }
+            }
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                Self::from_i64(0).expect("enum has zero variant")
+            }
+        }
+
+        impl core::fmt::Display for $name {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                write!(f, "{}", self.as_str())
+            }
+        }
+
+        impl core::str::FromStr for $name {
+            type Err = Error;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                $(if s.eq_ignore_ascii_case($json) {
+                    return Ok(Self::$variant);
+                })*
+                Err(Error::InvalidContract)
+            }
+        }
+
+        impl protobuf::Field for $name {
+            fn encode(&self, field: u32, w: &mut Writer) {
+                w.int64(field, *self as i64);
+            }
+
+            fn merge(slot: &mut Option<Self>, value: FieldValue<'_>) -> Result<(), Error> {
+                *slot = Some(Self::from_i64(value.as_i64()?)?);
+                Ok(())
+            }
+
+            fn missing() -> Result<Self, Error> {
+                Ok(Self::default())
+            }
+
+            fn to_json(&self) -> Option<Value> {
+                (*self as i64 != 0).then(|| Value::from(self.as_str()))
+            }
+
+            fn from_json(value: &Value) -> Result<Self, Error> {
+                match value {
+                    Value::String(s) => s.parse(),
+                    Value::Number(n) => Self::from_i64(n.as_i64().ok_or(Error::InvalidContract)?),
+                    _ => Err(Error::InvalidContract),
+                }
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: serde::Serializer,
+            {
+                serializer.serialize_str(self.as_str())
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                let value = Value::deserialize(deserializer)?;
+                protobuf::Field::from_json(&value).map_err(serde::de::Error::custom)
+            }
+        }
+    };
+}
+
+/// Declare ContractKind enum over contract messages with their `ContractType` ids
+macro_rules! contract_kinds {
+    ($($name:ident = $id:literal,)*) => {
+        /// Typed contract parameter, one variant per java-tron contract type
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub enum ContractKind {
+            $(
+                #[allow(missing_docs)]
+                $name($name),
+            )*
+            /// Contract type not modelled by this crate (only json form is available)
+            Other(OtherContract),
+        }
+
+        impl ContractKind {
+            /// Contract type name, e.g. `TransferContract`
+            pub fn type_name(&self) -> &str {
+                match self {
+                    $(Self::$name(_) => stringify!($name),)*
+                    Self::Other(c) => &c.r#type,
+                }
+            }
+
+            /// `ContractType` enum value
+            pub fn type_id(&self) -> Option<i32> {
+                match self {
+                    $(Self::$name(_) => Some($id),)*
+                    Self::Other(_) => None,
+                }
+            }
+
+            /// Encode parameter value (without `Any` wrapper)
+            pub fn encode_value(&self) -> Result<Vec<u8>, Error> {
+                match self {
+                    $(Self::$name(c) => Ok(c.encode()),)*
+                    Self::Other(_) => Err(Error::UnsupportedContract),
+                }
+            }
+
+            /// Decode parameter value of given `ContractType`
+            pub fn decode_value(type_id: i32, bytes: &[u8]) -> Result<Self, Error> {
+                match type_id {
+                    $($id => Ok(Self::$name($name::decode(bytes)?)),)*
+                    _ => Err(Error::UnsupportedContract),
+                }
+            }
+
+            /// Json form of parameter value
+            pub fn value_to_json(&self) -> Value {
+                match self {
+                    $(Self::$name(c) => c.to_json(),)*
+                    Self::Other(c) => c.value.clone(),
+                }
+            }
+
+            /// Parse json parameter value of given contract type
+            pub fn value_from_json(type_name: &str, value: &Value) -> Result<Self, Error> {
+                match type_name {
+                    $(stringify!($name) => Ok(Self::$name($name::from_json(value)?)),)*
+                    _ => Ok(Self::Other(OtherContract {
+                        r#type: type_name.to_string(),
+                        value: value.clone(),
+                    })),
+                }
+            }
+        }
+
+        $(
+            impl From<$name> for ContractKind {
+                fn from(contract: $name) -> Self {
+                    Self::$name(contract)
+                }
+            }
+        )*
+    };
+}
+
+proto_enum! {
+    /// Resource type (`ResourceCode`)
+    pub enum ResourceType {
+        /// Bandwidth resource
+        Bandwidth = 0 => "BANDWIDTH",
+        /// Energy resource
+        Energy = 1 => "ENERGY",
+        /// TRON power (voting)
+        TronPower = 2 => "TRON_POWER",
+    }
+}
+
+proto_enum! {
+    /// Account type
+    pub enum AccountType {
+        /// Regular account
+        Normal = 0 => "Normal",
+        /// Asset issue account
+        AssetIssue = 1 => "AssetIssue",
+        /// Contract account
+        Contract = 2 => "Contract",
+    }
+}
+
+proto_enum! {
+    /// Permission type
+    pub enum PermissionType {
+        /// Owner permission
+        Owner = 0 => "Owner",
+        /// Witness permission
+        Witness = 1 => "Witness",
+        /// Active permission
+        Active = 2 => "Active",
+    }
+}
+
+proto_enum! {
+    /// ABI entry type
+    pub enum AbiEntryType {
+        /// Unknown
+        Unknown = 0 => "UnknownEntryType",
+        /// Constructor
+        Constructor = 1 => "Constructor",
+        /// Function
+        Function = 2 => "Function",
+        /// Event
+        Event = 3 => "Event",
+        /// Fallback
+        Fallback = 4 => "Fallback",
+        /// Receive
+        Receive = 5 => "Receive",
+        /// Error
+        Error = 6 => "Error",
+    }
+}
+
+proto_enum! {
+    /// ABI entry state mutability
+    pub enum StateMutability {
+        /// Unknown
+        Unknown = 0 => "UnknownMutabilityType",
+        /// Pure
+        Pure = 1 => "Pure",
+        /// View
+        View = 2 => "View",
+        /// Nonpayable
+        Nonpayable = 3 => "Nonpayable",
+        /// Payable
+        Payable = 4 => "Payable",
+    }
+}
+
+message! {
+    /// Vote for a witness
+    pub struct Vote {
+        /// Witness address
+        pub vote_address: Address = 1,
+        /// Number of votes
+        pub vote_count: i64 = 2,
+    }
+}
+
+message! {
+    /// Permission key
+    pub struct Key {
+        /// Key address
+        pub address: Address = 1,
+        /// Key weight
+        pub weight: i64 = 2,
+    }
+}
+
+message! {
+    /// Account permission
+    pub struct Permission {
+        /// Permission type
+        pub r#type: PermissionType = 1 => "type",
+        /// Permission id: 0 for owner, 1 for witness, 2.. for actives
+        pub id: i32 = 2,
+        /// Permission name
+        pub permission_name: String = 3,
+        /// Signature weight threshold
+        pub threshold: i64 = 4,
+        /// Parent permission id
+        pub parent_id: i32 = 5,
+        /// Allowed contract types bitmap (active permissions only)
+        pub operations: Vec<u8> = 6,
+        /// Keys
+        pub keys: Vec<Key> = 7,
+    }
+}
+
+message! {
+    /// Frozen supply of an asset
+    pub struct FrozenSupply {
+        /// Frozen amount
+        pub frozen_amount: i64 = 1,
+        /// Frozen days
+        pub frozen_days: i64 = 2,
+    }
+}
+
+message! {
+    /// ABI entry parameter
+    pub struct AbiParam {
+        /// Indexed (events only)
+        pub indexed: bool = 1,
+        /// Parameter name
+        pub name: String = 2,
+        /// Parameter type, e.g. `uint256`
+        pub r#type: String = 3 => "type",
+    }
+}
+
+message! {
+    /// ABI entry
+    pub struct AbiEntry {
+        /// Anonymous (events only)
+        pub anonymous: bool = 1,
+        /// Constant
+        pub constant: bool = 2,
+        /// Entry name
+        pub name: String = 3,
+        /// Inputs
+        pub inputs: Vec<AbiParam> = 4,
+        /// Outputs
+        pub outputs: Vec<AbiParam> = 5,
+        /// Entry type
+        pub r#type: AbiEntryType = 6 => "type",
+        /// Payable
+        pub payable: bool = 7,
+        /// State mutability
+        pub state_mutability: StateMutability = 8 => "stateMutability",
+    }
+}
+
+message! {
+    /// Contract ABI
+    pub struct Abi {
+        /// ABI entries
+        pub entrys: Vec<AbiEntry> = 1,
+    }
+}
+
+message! {
+    /// Smart contract definition
+    pub struct SmartContract {
+        /// Deployer address
+        pub origin_address: Option<Address> = 1,
+        /// Contract address (filled in by the node after deployment)
+        pub contract_address: Option<Address> = 2,
+        /// ABI
+        pub abi: Option<Abi> = 3,
+        /// Bytecode
+        pub bytecode: Vec<u8> = 4,
+        /// Amount of SUN sent to constructor
+        pub call_value: i64 = 5,
+        /// Percentage of energy paid by the caller
+        pub consume_user_resource_percent: i64 = 6,
+        /// Contract name
+        pub name: String = 7,
+        /// Max energy provided by deployer per call
+        pub origin_energy_limit: i64 = 8,
+        /// Code hash
+        pub code_hash: Vec<u8> = 9,
+        /// Deploy transaction hash
+        pub trx_hash: Vec<u8> = 10,
+        /// Version
+        pub version: i32 = 11,
+    }
+}
+
+message! {
+    /// Activate a new account
+    pub struct AccountCreateContract {
+        /// Payer address
+        pub owner_address: Address = 1,
+        /// New account address
+        pub account_address: Address = 2,
+        /// Account type
+        pub r#type: AccountType = 3 => "type",
+    }
+}
+
+message! {
+    /// Transfer TRX
+    pub struct TransferContract {
+        /// Sender address
+        pub owner_address: Address = 1,
+        /// Receiver address
+        pub to_address: Address = 2,
+        /// Amount in SUN
+        pub amount: i64 = 3,
+    }
+}
+
+message! {
+    /// Transfer TRC10 asset
+    pub struct TransferAssetContract {
+        /// Asset id
+        pub asset_name: Vec<u8> = 1,
+        /// Sender address
+        pub owner_address: Address = 2,
+        /// Receiver address
+        pub to_address: Address = 3,
+        /// Amount
+        pub amount: i64 = 4,
+    }
+}
+
+message! {
+    /// Vote for assets (unused)
+    pub struct VoteAssetContract {
+        /// Voter address
+        pub owner_address: Address = 1,
+        /// Voted addresses
+        pub vote_address: Vec<Address> = 2,
+        /// Support
+        pub support: bool = 3,
+        /// Count
+        pub count: i32 = 5,
+    }
+}
+
+message! {
+    /// Vote for super representatives
+    pub struct VoteWitnessContract {
+        /// Voter address
+        pub owner_address: Address = 1,
+        /// Votes
+        pub votes: Vec<Vote> = 2,
+        /// Support
+        pub support: bool = 3,
+    }
+}
+
+message! {
+    /// Apply to become a super representative candidate
+    pub struct WitnessCreateContract {
+        /// Account address
+        pub owner_address: Address = 1,
+        /// Website url
+        pub url: Vec<u8> = 2,
+    }
+}
+
+message! {
+    /// Issue TRC10 asset
+    pub struct AssetIssueContract {
+        /// Issuer address
+        pub owner_address: Address = 1,
+        /// Asset name
+        pub name: Vec<u8> = 2,
+        /// Asset abbreviation
+        pub abbr: Vec<u8> = 3,
+        /// Total supply
+        pub total_supply: i64 = 4,
+        /// Frozen supply
+        pub frozen_supply: Vec<FrozenSupply> = 5,
+        /// TRX amount (in SUN) of the exchange rate
+        pub trx_num: i32 = 6,
+        /// Precision
+        pub precision: i32 = 7,
+        /// Asset amount of the exchange rate
+        pub num: i32 = 8,
+        /// ICO start time (ms)
+        pub start_time: i64 = 9,
+        /// ICO end time (ms)
+        pub end_time: i64 = 10,
+        /// Order
+        pub order: i64 = 11,
+        /// Vote score
+        pub vote_score: i32 = 16,
+        /// Description
+        pub description: Vec<u8> = 20,
+        /// Website url
+        pub url: Vec<u8> = 21,
+        /// Free bandwidth per account
+        pub free_asset_net_limit: i64 = 22,
+        /// Total free bandwidth
+        pub public_free_asset_net_limit: i64 = 23,
+        /// Used total free bandwidth
+        pub public_free_asset_net_usage: i64 = 24,
+        /// Last free bandwidth usage time
+        pub public_latest_free_net_time: i64 = 25,
+        /// Asset id
+        pub id: String = 41,
+    }
+}
+
+message! {
+    /// Update super representative website url
+    pub struct WitnessUpdateContract {
+        /// Witness address
+        pub owner_address: Address = 1,
+        /// New url
+        pub update_url: Vec<u8> = 12,
+    }
+}
+
+message! {
+    /// Participate in TRC10 asset ICO
+    pub struct ParticipateAssetIssueContract {
+        /// Participant address
+        pub owner_address: Address = 1,
+        /// Issuer address
+        pub to_address: Address = 2,
+        /// Asset id
+        pub asset_name: Vec<u8> = 3,
+        /// Amount of SUN to spend
+        pub amount: i64 = 4,
+    }
+}
+
+message! {
+    /// Set account name
+    pub struct AccountUpdateContract {
+        /// Account name
+        pub account_name: Vec<u8> = 1,
+        /// Account address
+        pub owner_address: Address = 2,
+    }
+}
+
+message! {
+    /// Stake 1.0 freeze (deprecated)
+    pub struct FreezeBalanceContract {
+        /// Account address
+        pub owner_address: Address = 1,
+        /// Amount in SUN
+        pub frozen_balance: i64 = 2,
+        /// Frozen days
+        pub frozen_duration: i64 = 3,
+        /// Resource type
+        pub resource: ResourceType = 10,
+        /// Resource receiver
+        pub receiver_address: Option<Address> = 15,
+    }
+}
+
+message! {
+    /// Stake 1.0 unfreeze
+    pub struct UnfreezeBalanceContract {
+        /// Account address
+        pub owner_address: Address = 1,
+        /// Resource type
+        pub resource: ResourceType = 10,
+        /// Resource receiver
+        pub receiver_address: Option<Address> = 13,
+    }
+}
+
+message! {
+    /// Withdraw voting rewards
+    pub struct WithdrawBalanceContract {
+        /// Account address
+        pub owner_address: Address = 1,
+    }
+}
+
+message! {
+    /// Unfreeze TRC10 frozen supply
+    pub struct UnfreezeAssetContract {
+        /// Issuer address
+        pub owner_address: Address = 1,
+    }
+}
+
+message! {
+    /// Update TRC10 asset parameters
+    pub struct UpdateAssetContract {
+        /// Issuer address
+        pub owner_address: Address = 1,
+        /// Description
+        pub description: Vec<u8> = 2,
+        /// Website url
+        pub url: Vec<u8> = 3,
+        /// Free bandwidth per account
+        pub new_limit: i64 = 4,
+        /// Total free bandwidth
+        pub new_public_limit: i64 = 5,
+    }
+}
+
+message! {
+    /// Create committee proposal
+    pub struct ProposalCreateContract {
+        /// Proposer address
+        pub owner_address: Address = 1,
+        /// Chain parameter id => new value
+        pub parameters: BTreeMap<i64, i64> = 2,
+    }
+}
+
+message! {
+    /// Approve (or withdraw approval of) committee proposal
+    pub struct ProposalApproveContract {
+        /// Approver address
+        pub owner_address: Address = 1,
+        /// Proposal id
+        pub proposal_id: i64 = 2,
+        /// Add (true) or remove (false) approval
+        pub is_add_approval: bool = 3,
+    }
+}
+
+message! {
+    /// Delete committee proposal
+    pub struct ProposalDeleteContract {
+        /// Proposer address
+        pub owner_address: Address = 1,
+        /// Proposal id
+        pub proposal_id: i64 = 2,
+    }
+}
+
+message! {
+    /// Set account id
+    pub struct SetAccountIdContract {
+        /// Account id
+        pub account_id: Vec<u8> = 1,
+        /// Account address
+        pub owner_address: Address = 2,
+    }
+}
+
+message! {
+    /// Deploy smart contract
+    pub struct CreateSmartContract {
+        /// Deployer address
+        pub owner_address: Address = 1,
+        /// Contract definition
+        pub new_contract: SmartContract = 2,
+        /// Amount of TRC10 token sent to constructor
+        pub call_token_value: i64 = 3,
+        /// TRC10 token id
+        pub token_id: i64 = 4,
+    }
+}
+
+message! {
+    /// Call smart contract
+    pub struct TriggerSmartContract {
+        /// Caller address
+        pub owner_address: Address = 1,
+        /// Contract address
+        pub contract_address: Address = 2,
+        /// Amount of SUN sent along with the call
+        pub call_value: i64 = 3,
+        /// ABI encoded call data (selector + arguments)
+        pub data: Vec<u8> = 4,
+        /// Amount of TRC10 token sent along with the call
+        pub call_token_value: i64 = 5,
+        /// TRC10 token id
+        pub token_id: i64 = 6,
+    }
+}
+
+message! {
+    /// Update contract energy share of the caller
+    pub struct UpdateSettingContract {
+        /// Contract owner address
+        pub owner_address: Address = 1,
+        /// Contract address
+        pub contract_address: Address = 2,
+        /// Percentage of energy paid by the caller
+        pub consume_user_resource_percent: i64 = 3,
+    }
+}
+
+message! {
+    /// Create Bancor exchange pair
+    pub struct ExchangeCreateContract {
+        /// Creator address
+        pub owner_address: Address = 1,
+        /// First token id (`_` for TRX)
+        pub first_token_id: Vec<u8> = 2,
+        /// First token balance
+        pub first_token_balance: i64 = 3,
+        /// Second token id
+        pub second_token_id: Vec<u8> = 4,
+        /// Second token balance
+        pub second_token_balance: i64 = 5,
+    }
+}
+
+message! {
+    /// Inject funds into exchange pair
+    pub struct ExchangeInjectContract {
+        /// Creator address
+        pub owner_address: Address = 1,
+        /// Exchange id
+        pub exchange_id: i64 = 2,
+        /// Token id
+        pub token_id: Vec<u8> = 3,
+        /// Amount
+        pub quant: i64 = 4,
+    }
+}
+
+message! {
+    /// Withdraw funds from exchange pair
+    pub struct ExchangeWithdrawContract {
+        /// Creator address
+        pub owner_address: Address = 1,
+        /// Exchange id
+        pub exchange_id: i64 = 2,
+        /// Token id
+        pub token_id: Vec<u8> = 3,
+        /// Amount
+        pub quant: i64 = 4,
+    }
+}
+
+message! {
+    /// Trade on exchange pair
+    pub struct ExchangeTransactionContract {
+        /// Trader address
+        pub owner_address: Address = 1,
+        /// Exchange id
+        pub exchange_id: i64 = 2,
+        /// Sold token id
+        pub token_id: Vec<u8> = 3,
+        /// Sold amount
+        pub quant: i64 = 4,
+        /// Minimum expected amount
+        pub expected: i64 = 5,
+    }
+}
+
+message! {
+    /// Update energy limit provided by contract deployer
+    pub struct UpdateEnergyLimitContract {
+        /// Contract owner address
+        pub owner_address: Address = 1,
+        /// Contract address
+        pub contract_address: Address = 2,
+        /// Max energy provided by deployer per call
+        pub origin_energy_limit: i64 = 3,
+    }
+}
+
+message! {
+    /// Update account permissions (multi-signature)
+    pub struct AccountPermissionUpdateContract {
+        /// Account address
+        pub owner_address: Address = 1,
+        /// Owner permission
+        pub owner: Option<Permission> = 2,
+        /// Witness permission
+        pub witness: Option<Permission> = 3,
+        /// Active permissions
+        pub actives: Vec<Permission> = 4,
+    }
+}
+
+message! {
+    /// Clear contract ABI
+    pub struct ClearABIContract {
+        /// Contract owner address
+        pub owner_address: Address = 1,
+        /// Contract address
+        pub contract_address: Address = 2,
+    }
+}
+
+message! {
+    /// Update super representative brokerage
+    pub struct UpdateBrokerageContract {
+        /// Witness address
+        pub owner_address: Address = 1,
+        /// Brokerage percentage
+        pub brokerage: i32 = 2,
+    }
+}
+
+message! {
+    /// Place order on DEX market
+    pub struct MarketSellAssetContract {
+        /// Seller address
+        pub owner_address: Address = 1,
+        /// Sold token id
+        pub sell_token_id: Vec<u8> = 2,
+        /// Sold amount
+        pub sell_token_quantity: i64 = 3,
+        /// Bought token id
+        pub buy_token_id: Vec<u8> = 4,
+        /// Minimum bought amount
+        pub buy_token_quantity: i64 = 5,
+    }
+}
+
+message! {
+    /// Cancel DEX market order
+    pub struct MarketCancelOrderContract {
+        /// Seller address
+        pub owner_address: Address = 1,
+        /// Order id
+        pub order_id: Vec<u8> = 2,
+    }
+}
+
+message! {
+    /// Stake 2.0 freeze TRX for resource
+    pub struct FreezeBalanceV2Contract {
+        /// Account address
+        pub owner_address: Address = 1,
+        /// Amount in SUN
+        pub frozen_balance: i64 = 2,
+        /// Resource type
+        pub resource: ResourceType = 3,
+    }
+}
+
+message! {
+    /// Stake 2.0 unfreeze TRX
+    pub struct UnfreezeBalanceV2Contract {
+        /// Account address
+        pub owner_address: Address = 1,
+        /// Amount in SUN
+        pub unfreeze_balance: i64 = 2,
+        /// Resource type
+        pub resource: ResourceType = 3,
+    }
+}
+
+message! {
+    /// Stake 2.0 withdraw unfrozen TRX after waiting period
+    pub struct WithdrawExpireUnfreezeContract {
+        /// Account address
+        pub owner_address: Address = 1,
+    }
+}
+
+message! {
+    /// Stake 2.0 delegate resource to another account
+    pub struct DelegateResourceContract {
+        /// Account address
+        pub owner_address: Address = 1,
+        /// Resource type
+        pub resource: ResourceType = 2,
+        /// Amount of staked SUN to delegate
+        pub balance: i64 = 3,
+        /// Resource receiver
+        pub receiver_address: Address = 4,
+        /// Lock delegation
+        pub lock: bool = 5,
+        /// Lock period in blocks
+        pub lock_period: i64 = 6,
+    }
+}
+
+message! {
+    /// Stake 2.0 undelegate resource
+    pub struct UnDelegateResourceContract {
+        /// Account address
+        pub owner_address: Address = 1,
+        /// Resource type
+        pub resource: ResourceType = 2,
+        /// Amount of staked SUN to undelegate
+        pub balance: i64 = 3,
+        /// Resource receiver
+        pub receiver_address: Address = 4,
+    }
+}
+
+message! {
+    /// Stake 2.0 cancel all pending unfreezes
+    pub struct CancelAllUnfreezeV2Contract {
+        /// Account address
+        pub owner_address: Address = 1,
+    }
+}
+
+/// Contract of a type not modelled by this crate (e.g. `ShieldedTransferContract`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OtherContract {
+    /// Contract type name
+    pub r#type: String,
+    /// Json parameter value as returned by node
+    pub value: Value,
+}
+
+contract_kinds! {
+    AccountCreateContract = 0,
+    TransferContract = 1,
+    TransferAssetContract = 2,
+    VoteAssetContract = 3,
+    VoteWitnessContract = 4,
+    WitnessCreateContract = 5,
+    AssetIssueContract = 6,
+    WitnessUpdateContract = 8,
+    ParticipateAssetIssueContract = 9,
+    AccountUpdateContract = 10,
+    FreezeBalanceContract = 11,
+    UnfreezeBalanceContract = 12,
+    WithdrawBalanceContract = 13,
+    UnfreezeAssetContract = 14,
+    UpdateAssetContract = 15,
+    ProposalCreateContract = 16,
+    ProposalApproveContract = 17,
+    ProposalDeleteContract = 18,
+    SetAccountIdContract = 19,
+    CreateSmartContract = 30,
+    TriggerSmartContract = 31,
+    UpdateSettingContract = 33,
+    ExchangeCreateContract = 41,
+    ExchangeInjectContract = 42,
+    ExchangeWithdrawContract = 43,
+    ExchangeTransactionContract = 44,
+    UpdateEnergyLimitContract = 45,
+    AccountPermissionUpdateContract = 46,
+    ClearABIContract = 48,
+    UpdateBrokerageContract = 49,
+    MarketSellAssetContract = 52,
+    MarketCancelOrderContract = 53,
+    FreezeBalanceV2Contract = 54,
+    UnfreezeBalanceV2Contract = 55,
+    WithdrawExpireUnfreezeContract = 56,
+    DelegateResourceContract = 57,
+    UnDelegateResourceContract = 58,
+    CancelAllUnfreezeV2Contract = 59,
+}
+
+impl ContractKind {
+    /// `google.protobuf.Any` type url of parameter
+    pub fn type_url(&self) -> String {
+        format!("type.googleapis.com/protocol.{}", self.type_name())
+    }
+
+    /// Owner (signer) address of contract, if known
+    pub fn owner_address(&self) -> Option<Address> {
+        match self {
+            Self::Other(c) => c.value.get("owner_address")?.as_str()?.parse().ok(),
+            _ => self
+                .value_to_json()
+                .get("owner_address")?
+                .as_str()?
+                .parse()
+                .ok(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_enum_json() {
+        assert_eq!(
+            serde_json::to_string(&ResourceType::TronPower).unwrap(),
+            "\"TRON_POWER\""
+        );
+        let energy: ResourceType = serde_json::from_str("\"ENERGY\"").unwrap();
+        assert_eq!(energy, ResourceType::Energy);
+        let entry_type: AbiEntryType = serde_json::from_str("\"function\"").unwrap();
+        assert_eq!(entry_type, AbiEntryType::Function);
+        assert_eq!(ResourceType::default(), ResourceType::Bandwidth);
+    }
+
+    #[test]
+    fn test_transfer_roundtrip() {
+        let json = serde_json::json!({
+            "amount": 1000,
+            "owner_address": "41608f8da72479edc7dd921e4c30bb7e7cddbe722e",
+            "to_address": "41e9d79cc47518930bc322d9bf7cddd260a0260a8d"
+        });
+        let contract: TransferContract = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(contract.amount, 1000);
+        assert_eq!(serde_json::to_value(&contract).unwrap(), json);
+        let bytes = contract.encode();
+        assert_eq!(hex::encode(&bytes), "0a1541608f8da72479edc7dd921e4c30bb7e7cddbe722e121541e9d79cc47518930bc322d9bf7cddd260a0260a8d18e807");
+        assert_eq!(TransferContract::decode(&bytes).unwrap(), contract);
+    }
+
+    #[test]
+    fn test_create_smart_contract_roundtrip() {
+        let json = serde_json::json!({
+            "owner_address": "418840e6c55b9ada326d211d818c34a994aeced808",
+            "new_contract": {
+                "origin_address": "418840e6c55b9ada326d211d818c34a994aeced808",
+                "abi": {
+                    "entrys": [{
+                        "name": "set",
+                        "inputs": [
+                            {"name": "key", "type": "uint256"},
+                            {"name": "value", "type": "uint256"}
+                        ],
+                        "type": "Function",
+                        "stateMutability": "Nonpayable"
+                    }, {
+                        "constant": true,
+                        "name": "get",
+                        "inputs": [{"name": "key", "type": "uint256"}],
+                        "outputs": [{"name": "value", "type": "uint256"}],
+                        "type": "Function",
+                        "stateMutability": "View"
+                    }]
+                },
+                "bytecode": "6080604052",
+                "consume_user_resource_percent": 100,
+                "name": "SmartContract",
+                "origin_energy_limit": 10000000
+            }
+        });
+        let contract = ContractKind::value_from_json("CreateSmartContract", &json).unwrap();
+        let ContractKind::CreateSmartContract(create) = &contract else {
+            panic!("wrong contract kind");
+        };
+        let abi = create.new_contract.abi.as_ref().unwrap();
+        assert_eq!(abi.entrys[1].state_mutability, StateMutability::View);
+        assert_eq!(contract.value_to_json(), json);
+        let decoded = ContractKind::decode_value(30, &contract.encode_value().unwrap()).unwrap();
+        assert_eq!(decoded, contract);
+        assert_eq!(
+            contract.owner_address().unwrap().as_base58(),
+            "TNPeeaaFB7K9cmo4uQpcU32zGK8G1NYqeL"
+        );
+    }
+
+    #[test]
+    fn test_permission_update_roundtrip() {
+        let owner: Address = "TNPeeaaFB7K9cmo4uQpcU32zGK8G1NYqeL".parse().unwrap();
+        let contract = AccountPermissionUpdateContract {
+            owner_address: owner,
+            owner: Some(Permission {
+                r#type: PermissionType::Owner,
+                id: 0,
+                permission_name: "owner".into(),
+                threshold: 2,
+                parent_id: 0,
+                operations: Vec::new(),
+                keys: alloc::vec![Key {
+                    address: owner,
+                    weight: 2
+                }],
+            }),
+            witness: None,
+            actives: alloc::vec![Permission {
+                r#type: PermissionType::Active,
+                id: 2,
+                permission_name: "active".into(),
+                threshold: 1,
+                parent_id: 0,
+                operations: hex::decode(
+                    "7fff1fc0033e0000000000000000000000000000000000000000000000000000"
+                )
+                .unwrap(),
+                keys: alloc::vec![Key {
+                    address: owner,
+                    weight: 1
+                }],
+            }],
+        };
+        let decoded = AccountPermissionUpdateContract::decode(&contract.encode()).unwrap();
+        assert_eq!(decoded, contract);
+        let json = contract.to_json();
+        assert_eq!(json["actives"][0]["type"], "Active");
+        assert!(json["owner"].get("type").is_none());
+        assert_eq!(
+            AccountPermissionUpdateContract::from_json(&json).unwrap(),
+            contract
+        );
+    }
+
+    #[test]
+    fn test_proposal_parameters() {
+        let json = serde_json::json!({
+            "owner_address": "418840e6c55b9ada326d211d818c34a994aeced808",
+            "parameters": [{"key": 11, "value": 420}, {"key": 0, "value": 1}]
+        });
+        let contract: ProposalCreateContract = serde_json::from_value(json).unwrap();
+        assert_eq!(contract.parameters.get(&11), Some(&420));
+        let bytes = contract.encode();
+        assert_eq!(ProposalCreateContract::decode(&bytes).unwrap(), contract);
+    }
+
+    #[test]
+    fn test_missing_required_field() {
+        let json = serde_json::json!({ "amount": 1 });
+        assert!(serde_json::from_value::<TransferContract>(json).is_err());
+        assert!(matches!(
+            TransferContract::decode(&[]),
+            Err(Error::InvalidContract)
+        ));
+    }
+}
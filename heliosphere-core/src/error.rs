@@ -1,12 +1,36 @@
 /// Error type
 #[derive(Debug, Clone)]
 pub enum Error {
-    /// Invalid address bytes or address string
-    InvalidAddress,
+    /// Address has wrong number of bytes (or hex / base58 characters)
+    InvalidAddressLength,
+    /// Base58 address checksum mismatch
+    InvalidAddressChecksum,
+    /// Address doesn't start with 0x41 byte
+    InvalidAddressPrefix,
+    /// Address string contains non hex / base58 characters
+    InvalidAddressCharacter,
     /// Invalid transaction id
     InvalidTransactionId,
     /// Invalid block id
     InvalidBlockId,
+    /// Contract type is not supported by protobuf encoding
+    UnsupportedContract,
+    /// Invalid or missing contract parameter
+    InvalidContract,
+    /// Invalid raw transaction data
+    InvalidTxData,
+    /// Malformed protobuf message
+    InvalidProtobuf,
+    /// Transaction id does not match sha256 of raw data
+    TxIdMismatch,
+    /// Invalid transaction signature bytes
+    InvalidSignature,
+    /// Invalid, negative or overflowing amount
+    InvalidAmount,
+    /// Reference block (or timestamp) is required to build transaction
+    MissingRefBlock,
+    /// Transaction expiration time overflows
+    InvalidExpiration,
 }
 
 impl core::fmt::Display for Error {
@@ -6,9 +6,14 @@
 extern crate alloc;
 
 mod address;
+pub mod amount;
 pub mod block;
+pub mod contract;
+pub mod proposal;
+pub mod protobuf;
 pub mod transaction;
 pub mod util;
 pub use address::Address;
+pub use amount::{Sun, TokenAmount};
 mod error;
 pub use error::Error;
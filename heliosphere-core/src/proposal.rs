@@ -0,0 +1,110 @@
+//! Committee proposal parameters
+//!
+//! Proposals refer to chain parameters by numeric id, `/wallet/getchainparameters` by name.
+
+/// Chain parameters which can be changed by proposals: (id, name as returned by `/wallet/getchainparameters`)
+pub const CHAIN_PARAMETERS: &[(i64, &str)] = &[
+    (0, "getMaintenanceTimeInterval"),
+    (1, "getAccountUpgradeCost"),
+    (2, "getCreateAccountFee"),
+    (3, "getTransactionFee"),
+    (4, "getAssetIssueFee"),
+    (5, "getWitnessPayPerBlock"),
+    (6, "getWitnessStandbyAllowance"),
+    (7, "getCreateNewAccountFeeInSystemContract"),
+    (8, "getCreateNewAccountBandwidthRate"),
+    (9, "getAllowCreationOfContracts"),
+    (10, "getRemoveThePowerOfTheGr"),
+    (11, "getEnergyFee"),
+    (12, "getExchangeCreateFee"),
+    (13, "getMaxCpuTimeOfOneTx"),
+    (14, "getAllowUpdateAccountName"),
+    (15, "getAllowSameTokenName"),
+    (16, "getAllowDelegateResource"),
+    (17, "getTotalEnergyLimit"),
+    (18, "getAllowTvmTransferTrc10"),
+    (19, "getTotalEnergyCurrentLimit"),
+    (20, "getAllowMultiSign"),
+    (21, "getAllowAdaptiveEnergy"),
+    (22, "getUpdateAccountPermissionFee"),
+    (23, "getMultiSignFee"),
+    (24, "getAllowProtoFilterNum"),
+    (25, "getAllowAccountStateRoot"),
+    (26, "getAllowTvmConstantinople"),
+    (29, "getAdaptiveResourceLimitMultiplier"),
+    (30, "getChangeDelegation"),
+    (31, "getWitness127PayPerBlock"),
+    (32, "getAllowTvmSolidity059"),
+    (33, "getAdaptiveResourceLimitTargetRatio"),
+    (35, "getForbidTransferToContract"),
+    (39, "getAllowShieldedTRC20Transaction"),
+    (40, "getAllowPBFT"),
+    (41, "getAllowTvmIstanbul"),
+    (44, "getAllowMarketTransaction"),
+    (45, "getMarketSellFee"),
+    (46, "getMarketCancelFee"),
+    (47, "getMaxFeeLimit"),
+    (48, "getAllowTransactionFeePool"),
+    (49, "getAllowOptimizeBlackHole"),
+    (51, "getAllowNewResourceModel"),
+    (52, "getAllowTvmFreeze"),
+    (53, "getAllowAccountAssetOptimization"),
+    (59, "getAllowTvmVote"),
+    (60, "getAllowTvmCompatibleEvm"),
+    (61, "getFreeNetLimit"),
+    (62, "getTotalNetLimit"),
+    (63, "getAllowTvmLondon"),
+    (65, "getAllowHigherLimitForMaxCpuTimeOfOneTx"),
+    (66, "getAllowAssetOptimization"),
+    (67, "getAllowNewReward"),
+    (68, "getMemoFee"),
+    (69, "getAllowDelegateOptimization"),
+    (70, "getUnfreezeDelayDays"),
+    (71, "getAllowOptimizedReturnValueOfChainId"),
+    (72, "getAllowDynamicEnergy"),
+    (73, "getDynamicEnergyThreshold"),
+    (74, "getDynamicEnergyIncreaseFactor"),
+    (75, "getDynamicEnergyMaxFactor"),
+    (76, "getAllowTvmShangHai"),
+    (77, "getAllowCancelAllUnfreezeV2"),
+    (78, "getMaxDelegateLockPeriod"),
+    (79, "getAllowOldRewardOpt"),
+    (81, "getAllowEnergyAdjustment"),
+    (82, "getMaxCreateAccountTxSize"),
+    (83, "getAllowTvmCancun"),
+    (87, "getAllowStrictMath"),
+    (88, "getConsensusLogicOptimization"),
+    (89, "getAllowTvmBlob"),
+];
+
+/// Get chain parameter id by name
+pub fn parameter_id(name: &str) -> Option<i64> {
+    CHAIN_PARAMETERS
+        .iter()
+        .find(|(_, n)| *n == name)
+        .map(|(id, _)| *id)
+}
+
+/// Get chain parameter name by id
+pub fn parameter_name(id: i64) -> Option<&'static str> {
+    CHAIN_PARAMETERS
+        .iter()
+        .find(|(i, _)| *i == id)
+        .map(|(_, name)| *name)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parameters() {
+        assert_eq!(parameter_id("getEnergyFee"), Some(11));
+        assert_eq!(parameter_name(11), Some("getEnergyFee"));
+        assert_eq!(parameter_id("getUnknown"), None);
+        assert_eq!(parameter_name(27), None);
+        for (id, name) in CHAIN_PARAMETERS {
+            assert_eq!(parameter_id(name), Some(*id));
+        }
+    }
+}
@@ -0,0 +1,605 @@
+//! Minimal protobuf wire format encoding/decoding (java-tron compatible)
+use crate::{Address, Error};
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+use serde_json::Value;
+
+/// Varint wire type
+pub const WIRE_VARINT: u8 = 0;
+/// 64-bit wire type
+pub const WIRE_FIXED64: u8 = 1;
+/// Length-delimited wire type (bytes, strings, embedded messages)
+pub const WIRE_LEN: u8 = 2;
+/// 32-bit wire type
+pub const WIRE_FIXED32: u8 = 5;
+
+/// Append value as base 128 varint
+pub fn write_varint(buf: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        buf.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    buf.push(value as u8);
+}
+
+/// Read base 128 varint from the beginning of buffer and advance it
+pub fn read_varint(buf: &mut &[u8]) -> Result<u64, Error> {
+    let mut value = 0u64;
+    for (i, byte) in buf.iter().enumerate().take(10) {
+        if i == 9 && *byte > 1 {
+            return Err(Error::InvalidProtobuf);
+        }
+        value |= ((byte & 0x7f) as u64) << (7 * i);
+        if byte & 0x80 == 0 {
+            *buf = &buf[i + 1..];
+            return Ok(value);
+        }
+    }
+    Err(Error::InvalidProtobuf)
+}
+
+/// Protobuf message writer.
+/// Follows proto3 semantics: scalar fields with default (zero/empty) values are omitted.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Writer {
+    buf: Vec<u8>,
+}
+
+impl Writer {
+    /// Create empty writer
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn key(&mut self, field: u32, wire_type: u8) {
+        write_varint(&mut self.buf, ((field as u64) << 3) | wire_type as u64);
+    }
+
+    /// Write unsigned varint field (uint32, uint64, enum)
+    pub fn uint64(&mut self, field: u32, value: u64) -> &mut Self {
+        if value != 0 {
+            self.key(field, WIRE_VARINT);
+            write_varint(&mut self.buf, value);
+        }
+        self
+    }
+
+    /// Write signed varint field (int32, int64). Negative values take 10 bytes as in protobuf-java.
+    pub fn int64(&mut self, field: u32, value: i64) -> &mut Self {
+        self.uint64(field, value as u64)
+    }
+
+    /// Write bool field
+    pub fn bool(&mut self, field: u32, value: bool) -> &mut Self {
+        self.uint64(field, value as u64)
+    }
+
+    /// Write bytes field (omitted if empty)
+    pub fn bytes(&mut self, field: u32, value: &[u8]) -> &mut Self {
+        if !value.is_empty() {
+            self.len_delimited(field, value);
+        }
+        self
+    }
+
+    /// Write string field (omitted if empty)
+    pub fn string(&mut self, field: u32, value: &str) -> &mut Self {
+        self.bytes(field, value.as_bytes())
+    }
+
+    /// Write embedded message or repeated element (always written, even if empty)
+    pub fn message(&mut self, field: u32, encoded: &[u8]) -> &mut Self {
+        self.len_delimited(field, encoded);
+        self
+    }
+
+    fn len_delimited(&mut self, field: u32, value: &[u8]) {
+        self.key(field, WIRE_LEN);
+        write_varint(&mut self.buf, value.len() as u64);
+        self.buf.extend_from_slice(value);
+    }
+
+    /// Get encoded bytes
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.buf
+    }
+}
+
+/// Decoded field value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldValue<'a> {
+    /// Varint (int32, int64, uint64, bool, enum)
+    Varint(u64),
+    /// Fixed 64-bit value
+    Fixed64(u64),
+    /// Length-delimited value (bytes, string, embedded message)
+    Bytes(&'a [u8]),
+    /// Fixed 32-bit value
+    Fixed32(u32),
+}
+
+impl<'a> FieldValue<'a> {
+    /// Get varint value
+    pub fn as_u64(&self) -> Result<u64, Error> {
+        match self {
+            Self::Varint(x) => Ok(*x),
+            _ => Err(Error::InvalidProtobuf),
+        }
+    }
+
+    /// Get varint value as signed int64
+    pub fn as_i64(&self) -> Result<i64, Error> {
+        self.as_u64().map(|x| x as i64)
+    }
+
+    /// Get length-delimited value
+    pub fn as_bytes(&self) -> Result<&'a [u8], Error> {
+        match self {
+            Self::Bytes(x) => Ok(x),
+            _ => Err(Error::InvalidProtobuf),
+        }
+    }
+
+    /// Get length-delimited value as utf-8 string
+    pub fn as_str(&self) -> Result<&'a str, Error> {
+        core::str::from_utf8(self.as_bytes()?).map_err(|_| Error::InvalidProtobuf)
+    }
+}
+
+/// Protobuf message reader, iterates over (field number, value) pairs
+#[derive(Debug, Clone)]
+pub struct Reader<'a> {
+    buf: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    /// Create reader over encoded message
+    pub fn new(buf: &'a [u8]) -> Self {
+        Self { buf }
+    }
+
+    fn read_field(&mut self) -> Result<(u32, FieldValue<'a>), Error> {
+        let key = read_varint(&mut self.buf)?;
+        let field = u32::try_from(key >> 3).map_err(|_| Error::InvalidProtobuf)?;
+        if field == 0 {
+            return Err(Error::InvalidProtobuf);
+        }
+        let value = match (key & 0x07) as u8 {
+            WIRE_VARINT => FieldValue::Varint(read_varint(&mut self.buf)?),
+            WIRE_FIXED64 => FieldValue::Fixed64(u64::from_le_bytes(
+                self.take(8)?.try_into().expect("8 bytes"),
+            )),
+            WIRE_LEN => {
+                let len = read_varint(&mut self.buf)?;
+                FieldValue::Bytes(
+                    self.take(usize::try_from(len).map_err(|_| Error::InvalidProtobuf)?)?,
+                )
+            }
+            WIRE_FIXED32 => FieldValue::Fixed32(u32::from_le_bytes(
+                self.take(4)?.try_into().expect("4 bytes"),
+            )),
+            _ => return Err(Error::InvalidProtobuf),
+        };
+        Ok((field, value))
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], Error> {
+        if self.buf.len() < len {
+            return Err(Error::InvalidProtobuf);
+        }
+        let (head, tail) = self.buf.split_at(len);
+        self.buf = tail;
+        Ok(head)
+    }
+}
+
+impl<'a> Iterator for Reader<'a> {
+    type Item = Result<(u32, FieldValue<'a>), Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.buf.is_empty() {
+            return None;
+        }
+        let res = self.read_field();
+        if res.is_err() {
+            self.buf = &[]; // stop on malformed input
+        }
+        Some(res)
+    }
+}
+
+/// Protobuf message with json representation as used by java-tron HTTP API
+pub trait Message: Sized {
+    /// Encode message to protobuf
+    fn encode(&self) -> Vec<u8>;
+
+    /// Decode message from protobuf
+    fn decode(bytes: &[u8]) -> Result<Self, Error>;
+
+    /// Json object as returned by java-tron (hex addresses and bytes, default values omitted)
+    fn to_json(&self) -> Value;
+
+    /// Parse json object (addresses may be hex or base58)
+    fn from_json(value: &Value) -> Result<Self, Error>;
+}
+
+/// Message field: scalar, enum, embedded message or repeated value
+pub(crate) trait Field: Sized {
+    /// Encode as field number `field` (proto3 default values are omitted)
+    fn encode(&self, field: u32, w: &mut Writer);
+
+    /// Merge decoded wire value into field
+    fn merge(slot: &mut Option<Self>, value: FieldValue<'_>) -> Result<(), Error>;
+
+    /// Value of absent field
+    fn missing() -> Result<Self, Error>;
+
+    /// Json value (None if default and should be omitted)
+    fn to_json(&self) -> Option<Value>;
+
+    /// Parse json value
+    fn from_json(value: &Value) -> Result<Self, Error>;
+}
+
+impl Field for i64 {
+    fn encode(&self, field: u32, w: &mut Writer) {
+        w.int64(field, *self);
+    }
+
+    fn merge(slot: &mut Option<Self>, value: FieldValue<'_>) -> Result<(), Error> {
+        *slot = Some(value.as_i64()?);
+        Ok(())
+    }
+
+    fn missing() -> Result<Self, Error> {
+        Ok(0)
+    }
+
+    fn to_json(&self) -> Option<Value> {
+        (*self != 0).then(|| Value::from(*self))
+    }
+
+    fn from_json(value: &Value) -> Result<Self, Error> {
+        value.as_i64().ok_or(Error::InvalidContract)
+    }
+}
+
+impl Field for i32 {
+    fn encode(&self, field: u32, w: &mut Writer) {
+        w.int64(field, *self as i64);
+    }
+
+    fn merge(slot: &mut Option<Self>, value: FieldValue<'_>) -> Result<(), Error> {
+        *slot = Some(value.as_i64()? as i32);
+        Ok(())
+    }
+
+    fn missing() -> Result<Self, Error> {
+        Ok(0)
+    }
+
+    fn to_json(&self) -> Option<Value> {
+        (*self != 0).then(|| Value::from(*self))
+    }
+
+    fn from_json(value: &Value) -> Result<Self, Error> {
+        value
+            .as_i64()
+            .and_then(|x| x.try_into().ok())
+            .ok_or(Error::InvalidContract)
+    }
+}
+
+impl Field for bool {
+    fn encode(&self, field: u32, w: &mut Writer) {
+        w.bool(field, *self);
+    }
+
+    fn merge(slot: &mut Option<Self>, value: FieldValue<'_>) -> Result<(), Error> {
+        *slot = Some(value.as_u64()? != 0);
+        Ok(())
+    }
+
+    fn missing() -> Result<Self, Error> {
+        Ok(false)
+    }
+
+    fn to_json(&self) -> Option<Value> {
+        self.then_some(Value::Bool(true))
+    }
+
+    fn from_json(value: &Value) -> Result<Self, Error> {
+        value.as_bool().ok_or(Error::InvalidContract)
+    }
+}
+
+impl Field for Vec<u8> {
+    fn encode(&self, field: u32, w: &mut Writer) {
+        w.bytes(field, self);
+    }
+
+    fn merge(slot: &mut Option<Self>, value: FieldValue<'_>) -> Result<(), Error> {
+        *slot = Some(value.as_bytes()?.to_vec());
+        Ok(())
+    }
+
+    fn missing() -> Result<Self, Error> {
+        Ok(Vec::new())
+    }
+
+    fn to_json(&self) -> Option<Value> {
+        (!self.is_empty()).then(|| Value::from(hex::encode(self)))
+    }
+
+    fn from_json(value: &Value) -> Result<Self, Error> {
+        let s = value.as_str().ok_or(Error::InvalidContract)?;
+        hex::decode(s.trim_start_matches("0x")).map_err(|_| Error::InvalidContract)
+    }
+}
+
+impl Field for String {
+    fn encode(&self, field: u32, w: &mut Writer) {
+        w.string(field, self);
+    }
+
+    fn merge(slot: &mut Option<Self>, value: FieldValue<'_>) -> Result<(), Error> {
+        *slot = Some(value.as_str()?.into());
+        Ok(())
+    }
+
+    fn missing() -> Result<Self, Error> {
+        Ok(String::new())
+    }
+
+    fn to_json(&self) -> Option<Value> {
+        (!self.is_empty()).then(|| Value::from(self.as_str()))
+    }
+
+    fn from_json(value: &Value) -> Result<Self, Error> {
+        value.as_str().map(Into::into).ok_or(Error::InvalidContract)
+    }
+}
+
+impl Field for Address {
+    fn encode(&self, field: u32, w: &mut Writer) {
+        w.bytes(field, self.as_bytes());
+    }
+
+    fn merge(slot: &mut Option<Self>, value: FieldValue<'_>) -> Result<(), Error> {
+        let bytes = value.as_bytes()?;
+        *slot = Some(Address::new(
+            bytes.try_into().map_err(|_| Error::InvalidAddressLength)?,
+        )?);
+        Ok(())
+    }
+
+    fn missing() -> Result<Self, Error> {
+        Err(Error::InvalidContract)
+    }
+
+    fn to_json(&self) -> Option<Value> {
+        Some(Value::from(self.as_hex()))
+    }
+
+    fn from_json(value: &Value) -> Result<Self, Error> {
+        value.as_str().ok_or(Error::InvalidContract)?.parse()
+    }
+}
+
+impl Field for Option<Address> {
+    fn encode(&self, field: u32, w: &mut Writer) {
+        if let Some(address) = self {
+            address.encode(field, w);
+        }
+    }
+
+    fn merge(slot: &mut Option<Self>, value: FieldValue<'_>) -> Result<(), Error> {
+        let mut address = None;
+        Address::merge(&mut address, value)?;
+        *slot = Some(address);
+        Ok(())
+    }
+
+    fn missing() -> Result<Self, Error> {
+        Ok(None)
+    }
+
+    fn to_json(&self) -> Option<Value> {
+        self.as_ref().and_then(Field::to_json)
+    }
+
+    fn from_json(value: &Value) -> Result<Self, Error> {
+        Address::from_json(value).map(Some)
+    }
+}
+
+impl Field for Vec<Address> {
+    fn encode(&self, field: u32, w: &mut Writer) {
+        for address in self {
+            w.message(field, address.as_bytes());
+        }
+    }
+
+    fn merge(slot: &mut Option<Self>, value: FieldValue<'_>) -> Result<(), Error> {
+        let mut address = None;
+        Address::merge(&mut address, value)?;
+        slot.get_or_insert_with(Vec::new).extend(address);
+        Ok(())
+    }
+
+    fn missing() -> Result<Self, Error> {
+        Ok(Vec::new())
+    }
+
+    fn to_json(&self) -> Option<Value> {
+        (!self.is_empty()).then(|| self.iter().filter_map(Field::to_json).collect())
+    }
+
+    fn from_json(value: &Value) -> Result<Self, Error> {
+        value
+            .as_array()
+            .ok_or(Error::InvalidContract)?
+            .iter()
+            .map(Address::from_json)
+            .collect()
+    }
+}
+
+impl<T: Message> Field for Option<T> {
+    fn encode(&self, field: u32, w: &mut Writer) {
+        if let Some(message) = self {
+            w.message(field, &message.encode());
+        }
+    }
+
+    fn merge(slot: &mut Option<Self>, value: FieldValue<'_>) -> Result<(), Error> {
+        *slot = Some(Some(T::decode(value.as_bytes()?)?));
+        Ok(())
+    }
+
+    fn missing() -> Result<Self, Error> {
+        Ok(None)
+    }
+
+    fn to_json(&self) -> Option<Value> {
+        self.as_ref().map(Message::to_json)
+    }
+
+    fn from_json(value: &Value) -> Result<Self, Error> {
+        T::from_json(value).map(Some)
+    }
+}
+
+impl<T: Message> Field for Vec<T> {
+    fn encode(&self, field: u32, w: &mut Writer) {
+        for message in self {
+            w.message(field, &message.encode());
+        }
+    }
+
+    fn merge(slot: &mut Option<Self>, value: FieldValue<'_>) -> Result<(), Error> {
+        slot.get_or_insert_with(Vec::new)
+            .push(T::decode(value.as_bytes()?)?);
+        Ok(())
+    }
+
+    fn missing() -> Result<Self, Error> {
+        Ok(Vec::new())
+    }
+
+    fn to_json(&self) -> Option<Value> {
+        (!self.is_empty()).then(|| self.iter().map(Message::to_json).collect())
+    }
+
+    fn from_json(value: &Value) -> Result<Self, Error> {
+        value
+            .as_array()
+            .ok_or(Error::InvalidContract)?
+            .iter()
+            .map(T::from_json)
+            .collect()
+    }
+}
+
+/// `map<int64, int64>`, encoded in key order, json is a list of `{"key": .., "value": ..}`
+impl Field for BTreeMap<i64, i64> {
+    fn encode(&self, field: u32, w: &mut Writer) {
+        for (key, value) in self {
+            let mut entry = Writer::new();
+            entry.int64(1, *key).int64(2, *value);
+            w.message(field, &entry.into_bytes());
+        }
+    }
+
+    fn merge(slot: &mut Option<Self>, value: FieldValue<'_>) -> Result<(), Error> {
+        let (mut key, mut val) = (0, 0);
+        for field in Reader::new(value.as_bytes()?) {
+            match field? {
+                (1, v) => key = v.as_i64()?,
+                (2, v) => val = v.as_i64()?,
+                _ => {}
+            }
+        }
+        slot.get_or_insert_with(BTreeMap::new).insert(key, val);
+        Ok(())
+    }
+
+    fn missing() -> Result<Self, Error> {
+        Ok(BTreeMap::new())
+    }
+
+    fn to_json(&self) -> Option<Value> {
+        (!self.is_empty()).then(|| {
+            self.iter()
+                .map(|(key, value)| serde_json::json!({ "key": key, "value": value }))
+                .collect()
+        })
+    }
+
+    fn from_json(value: &Value) -> Result<Self, Error> {
+        value
+            .as_array()
+            .ok_or(Error::InvalidContract)?
+            .iter()
+            .map(|entry| {
+                let get = |name| entry.get(name).map_or(Ok(0), i64::from_json);
+                Ok((get("key")?, get("value")?))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn test_varint() {
+        let mut buf = vec![];
+        write_varint(&mut buf, 1);
+        write_varint(&mut buf, 300);
+        write_varint(&mut buf, 1591089627000);
+        assert_eq!(buf, hex_literal::hex!("01 ac02 f8fed3a2a72e"));
+    }
+
+    #[test]
+    fn test_read_varint() {
+        let mut buf: &[u8] = &hex_literal::hex!("01 ac02 f8fed3a2a72e");
+        assert_eq!(read_varint(&mut buf).unwrap(), 1);
+        assert_eq!(read_varint(&mut buf).unwrap(), 300);
+        assert_eq!(read_varint(&mut buf).unwrap(), 1591089627000);
+        assert!(buf.is_empty());
+        assert!(read_varint(&mut &[0x80][..]).is_err());
+        assert!(read_varint(&mut &[0xff; 11][..]).is_err());
+    }
+
+    #[test]
+    fn test_reader() {
+        let buf = hex_literal::hex!("10ffffffffffffffffff01 22026162 2a00");
+        let fields: Vec<_> = Reader::new(&buf).collect::<Result<_, _>>().unwrap();
+        assert_eq!(
+            fields,
+            vec![
+                (2, FieldValue::Varint(u64::MAX)),
+                (4, FieldValue::Bytes(b"ab")),
+                (5, FieldValue::Bytes(&[]))
+            ]
+        );
+        assert_eq!(fields[0].1.as_i64().unwrap(), -1);
+        assert!(Reader::new(&hex_literal::hex!("2205")).any(|f| f.is_err()));
+    }
+
+    #[test]
+    fn test_writer() {
+        let mut w = Writer::new();
+        w.uint64(1, 0)
+            .int64(2, -1)
+            .bytes(3, &[])
+            .string(4, "ab")
+            .message(5, &[]);
+        assert_eq!(
+            w.into_bytes(),
+            hex_literal::hex!("10ffffffffffffffffff01 22026162 2a00")
+        );
+    }
+}
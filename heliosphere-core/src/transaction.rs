@@ -1,11 +1,21 @@
 //! Transaction definitions
 #![allow(missing_docs)]
-use crate::util::{as_hex_array, as_hex_buffer};
+use crate::{
+    block::{Block, BlockId},
+    contract::ContractKind,
+    protobuf::{Reader, Writer},
+    util::{as_hex_array, as_hex_buffer},
+    Error, Sun,
+};
 use alloc::{
     string::{String, ToString},
+    vec,
     vec::Vec,
 };
+use core::time::Duration;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
 
 /// Transaction id wrapper, serializable as hex string
 #[derive(Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, PartialOrd, Ord)]
@@ -36,6 +46,13 @@ impl core::str::FromStr for TransactionId {
     }
 }
 
+impl TransactionId {
+    /// Compute transaction id as sha256 of protobuf encoded raw data
+    pub fn from_raw_data_bytes(raw_data_bytes: &[u8]) -> Self {
+        Self(Sha256::digest(raw_data_bytes).into())
+    }
+}
+
 impl From<alloy_primitives::TxHash> for TransactionId {
     fn from(value: alloy_primitives::TxHash) -> Self {
         Self(value.0)
@@ -48,13 +65,221 @@ impl From<TransactionId> for alloy_primitives::TxHash {
     }
 }
 
-/// Contract call with json payload
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+/// Transaction signature `r || s || v`, serializable as hex string
+///
+/// Recovery id is normalized on parsing: both `v = 0/1` (java-tron) and `v = 27/28` (TronWeb) are accepted.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TronSignature {
+    /// Signature `r` value
+    pub r: [u8; 32],
+    /// Signature `s` value
+    pub s: [u8; 32],
+    /// Recovery id (0..=3)
+    pub recovery_id: u8,
+}
+
+impl TronSignature {
+    /// Create signature from `r`, `s` and recovery id (or `v` = 27 + recovery id)
+    pub fn new(r: [u8; 32], s: [u8; 32], v: u8) -> Result<Self, Error> {
+        let recovery_id = match v {
+            0..=3 => v,
+            27..=30 => v - 27,
+            _ => return Err(Error::InvalidSignature),
+        };
+        Ok(Self { r, s, recovery_id })
+    }
+
+    /// Parse 65-byte signature `r || s || v`
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, Error> {
+        if bytes.len() != 65 {
+            return Err(Error::InvalidSignature);
+        }
+        Self::new(
+            bytes[..32].try_into().expect("Always 32 bytes"),
+            bytes[32..64].try_into().expect("Always 32 bytes"),
+            bytes[64],
+        )
+    }
+
+    /// Get 65-byte signature `r || s || recovery_id` (java-tron form)
+    pub fn to_bytes(&self) -> [u8; 65] {
+        let mut bytes = [0; 65];
+        bytes[..32].copy_from_slice(&self.r);
+        bytes[32..64].copy_from_slice(&self.s);
+        bytes[64] = self.recovery_id;
+        bytes
+    }
+
+    /// Get `v` value (27 + recovery id)
+    pub fn v(&self) -> u8 {
+        27 + self.recovery_id
+    }
+}
+
+impl core::fmt::Display for TronSignature {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", hex::encode(self.to_bytes()))
+    }
+}
+
+impl core::fmt::Debug for TronSignature {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", hex::encode(self.to_bytes()))
+    }
+}
+
+impl core::str::FromStr for TronSignature {
+    type Err = crate::Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.strip_prefix("0x").unwrap_or(s);
+        Self::from_bytes(&hex::decode(s).map_err(|_| crate::Error::InvalidSignature)?)
+    }
+}
+
+impl Serialize for TronSignature {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for TronSignature {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// Transaction contract (typed parameter)
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Contract {
-    /// Param
-    pub parameter: serde_json::Value,
-    /// Type
-    pub r#type: String,
+    /// Contract parameter
+    pub kind: ContractKind,
+    /// Id of account permission used to sign the contract (0 for owner)
+    pub permission_id: i32,
+}
+
+impl Contract {
+    /// Create contract from typed parameter (signed with owner permission)
+    pub fn new(kind: impl Into<ContractKind>) -> Self {
+        Self {
+            kind: kind.into(),
+            permission_id: 0,
+        }
+    }
+
+    /// Encode contract to protobuf (`protocol.Transaction.Contract` message)
+    pub fn to_protobuf(&self) -> Result<Vec<u8>, Error> {
+        let type_id = self.kind.type_id().ok_or(Error::UnsupportedContract)?;
+        let mut any = Writer::new();
+        any.string(1, &self.kind.type_url())
+            .bytes(2, &self.kind.encode_value()?);
+        let mut contract = Writer::new();
+        contract
+            .int64(1, type_id as i64)
+            .message(2, &any.into_bytes())
+            .int64(5, self.permission_id as i64);
+        Ok(contract.into_bytes())
+    }
+
+    /// Decode contract from protobuf (`protocol.Transaction.Contract` message)
+    pub fn from_protobuf(bytes: &[u8]) -> Result<Self, Error> {
+        let mut type_id = 0;
+        let mut any: &[u8] = &[];
+        let mut permission_id = 0;
+        for field in Reader::new(bytes) {
+            match field? {
+                (1, v) => type_id = v.as_i64()? as i32,
+                (2, v) => any = v.as_bytes()?,
+                (5, v) => permission_id = v.as_i64()? as i32,
+                _ => {}
+            }
+        }
+        let mut type_url = "";
+        let mut value: &[u8] = &[];
+        for field in Reader::new(any) {
+            match field? {
+                (1, v) => type_url = v.as_str()?,
+                (2, v) => value = v.as_bytes()?,
+                _ => {}
+            }
+        }
+        let kind = ContractKind::decode_value(type_id, value)?;
+        if kind.type_url() != type_url {
+            return Err(Error::InvalidContract);
+        }
+        Ok(Self {
+            kind,
+            permission_id,
+        })
+    }
+}
+
+impl<T: Into<ContractKind>> From<T> for Contract {
+    fn from(kind: T) -> Self {
+        Self::new(kind)
+    }
+}
+
+/// Json form of contract: `{"parameter": {"value": {..}, "type_url": ".."}, "type": ".."}`
+#[derive(Serialize, Deserialize)]
+struct ContractJson {
+    parameter: ContractParameterJson,
+    r#type: String,
+    #[serde(
+        rename = "Permission_id",
+        default,
+        skip_serializing_if = "is_owner_permission"
+    )]
+    permission_id: i32,
+}
+
+fn is_owner_permission(permission_id: &i32) -> bool {
+    *permission_id == 0
+}
+
+#[derive(Serialize, Deserialize)]
+struct ContractParameterJson {
+    value: Value,
+    #[serde(default)]
+    type_url: String,
+}
+
+impl Serialize for Contract {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        ContractJson {
+            parameter: ContractParameterJson {
+                value: self.kind.value_to_json(),
+                type_url: self.kind.type_url(),
+            },
+            r#type: self.kind.type_name().to_string(),
+            permission_id: self.permission_id,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Contract {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let json = ContractJson::deserialize(deserializer)?;
+        let kind = ContractKind::value_from_json(&json.r#type, &json.parameter.value)
+            .map_err(serde::de::Error::custom)?;
+        Ok(Self {
+            kind,
+            permission_id: json.permission_id,
+        })
+    }
 }
 
 /// See tron docs for field description
@@ -74,6 +299,54 @@ pub struct RawTxData {
     pub ref_block_hash: Vec<u8>,
 }
 
+impl RawTxData {
+    /// Encode raw data to protobuf (`protocol.Transaction.raw` message), as used for `raw_data_hex`
+    pub fn to_protobuf(&self) -> Result<Vec<u8>, Error> {
+        let mut w = Writer::new();
+        w.bytes(1, &self.ref_block_bytes)
+            .bytes(4, &self.ref_block_hash)
+            .uint64(8, self.expiration);
+        if let Some(data) = &self.data {
+            w.bytes(10, &hex::decode(data).map_err(|_| Error::InvalidTxData)?);
+        }
+        for contract in &self.contract {
+            w.message(11, &contract.to_protobuf()?);
+        }
+        w.uint64(14, self.timestamp.unwrap_or_default())
+            .uint64(18, self.fee_limit);
+        Ok(w.into_bytes())
+    }
+
+    /// Decode raw data from protobuf (`protocol.Transaction.raw` message).
+    /// Unknown fields are skipped, use [Transaction::decode_raw_data] for strict verification.
+    pub fn from_protobuf(bytes: &[u8]) -> Result<Self, Error> {
+        let mut raw_data = Self {
+            data: None,
+            contract: Vec::new(),
+            expiration: 0,
+            timestamp: None,
+            fee_limit: 0,
+            ref_block_bytes: Vec::new(),
+            ref_block_hash: Vec::new(),
+        };
+        for field in Reader::new(bytes) {
+            match field? {
+                (1, v) => raw_data.ref_block_bytes = v.as_bytes()?.to_vec(),
+                (4, v) => raw_data.ref_block_hash = v.as_bytes()?.to_vec(),
+                (8, v) => raw_data.expiration = v.as_u64()?,
+                (10, v) => raw_data.data = Some(hex::encode(v.as_bytes()?)),
+                (11, v) => raw_data
+                    .contract
+                    .push(Contract::from_protobuf(v.as_bytes()?)?),
+                (14, v) => raw_data.timestamp = Some(v.as_u64()?),
+                (18, v) => raw_data.fee_limit = v.as_u64()?,
+                _ => {}
+            }
+        }
+        Ok(raw_data)
+    }
+}
+
 /// See tron docs for field description
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct Transaction {
@@ -83,10 +356,57 @@ pub struct Transaction {
     #[serde(rename = "txID")]
     pub tx_id: TransactionId,
     #[serde(default)]
-    pub signature: Vec<String>,
+    pub signature: Vec<TronSignature>,
 }
 
 impl Transaction {
+    /// Create unsigned transaction from raw data (encoded offline, tx id is computed locally).
+    /// Zero timestamp is not encoded, so it is normalized to `None` to match decoded raw data.
+    pub fn from_raw_data(mut raw_data: RawTxData) -> Result<Self, Error> {
+        raw_data.timestamp = raw_data.timestamp.filter(|t| *t != 0);
+        let raw_data_bytes = raw_data.to_protobuf()?;
+        Ok(Self {
+            tx_id: TransactionId::from_raw_data_bytes(&raw_data_bytes),
+            raw_data,
+            raw_data_bytes,
+            signature: Vec::new(),
+        })
+    }
+
+    /// Set id of account permission used to sign all contracts (0 for owner, 2.. for actives).
+    /// Raw data bytes and tx id are recomputed, existing signatures are dropped.
+    pub fn set_permission_id(&mut self, permission_id: i32) -> Result<(), Error> {
+        let mut raw_data = self.raw_data.clone();
+        for contract in &mut raw_data.contract {
+            contract.permission_id = permission_id;
+        }
+        *self = Self::from_raw_data(raw_data)?;
+        Ok(())
+    }
+
+    /// Id of account permission the transaction must be signed with
+    pub fn permission_id(&self) -> i32 {
+        self.raw_data
+            .contract
+            .first()
+            .map(|c| c.permission_id)
+            .unwrap_or_default()
+    }
+
+    /// Decode `raw_data_bytes`, checking that they hash to `tx_id` and contain nothing
+    /// but the decoded fields (re-encoding must give the very same bytes).
+    /// Use this to verify node-built transactions before signing.
+    pub fn decode_raw_data(&self) -> Result<RawTxData, Error> {
+        if TransactionId::from_raw_data_bytes(&self.raw_data_bytes) != self.tx_id {
+            return Err(Error::TxIdMismatch);
+        }
+        let raw_data = RawTxData::from_protobuf(&self.raw_data_bytes)?;
+        if raw_data.to_protobuf()? != self.raw_data_bytes {
+            return Err(Error::InvalidTxData);
+        }
+        Ok(raw_data)
+    }
+
     /// Get tx id as hex string
     pub fn tx_id_hex(&self) -> String {
         self.tx_id.to_string()
@@ -97,3 +417,338 @@ impl Transaction {
         hex::encode(&self.raw_data_bytes)
     }
 }
+
+/// Default transaction expiration window (same as java-tron)
+pub const DEFAULT_EXPIRATION: Duration = Duration::from_secs(60);
+
+/// Maximum expiration window accepted by java-tron
+pub const MAX_EXPIRATION: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Offline transaction builder
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransactionBuilder {
+    contract: Contract,
+    memo: Option<Vec<u8>>,
+    fee_limit: Sun,
+    expiration: Duration,
+    timestamp: Option<u64>,
+    ref_block_id: Option<BlockId>,
+    ref_block_timestamp: Option<u64>,
+}
+
+impl TransactionBuilder {
+    /// Create new builder for given contract
+    pub fn new(contract: impl Into<ContractKind>) -> Self {
+        Self {
+            contract: Contract::new(contract),
+            memo: None,
+            fee_limit: Sun::ZERO,
+            expiration: DEFAULT_EXPIRATION,
+            timestamp: None,
+            ref_block_id: None,
+            ref_block_timestamp: None,
+        }
+    }
+
+    /// Set reference block (TaPoS), expiration is counted from its timestamp unless
+    /// transaction timestamp is set. Should be one of the latest blocks (within 65535 blocks).
+    pub fn with_ref_block(mut self, block: &Block) -> Self {
+        self.ref_block_id = Some(block.block_id);
+        self.ref_block_timestamp = Some(block.block_header.raw_data.timestamp);
+        self
+    }
+
+    /// Set reference block by id only (block number is encoded in first 8 bytes of id).
+    /// Transaction timestamp must be set with [Self::with_timestamp] to compute expiration.
+    pub fn with_ref_block_id(mut self, block_id: BlockId) -> Self {
+        self.ref_block_id = Some(block_id);
+        self
+    }
+
+    /// Set transaction creation timestamp (ms)
+    pub fn with_timestamp(mut self, timestamp: u64) -> Self {
+        self.timestamp = Some(timestamp);
+        self
+    }
+
+    /// Set expiration window (default 60 seconds)
+    pub fn with_expiration(mut self, expiration: Duration) -> Self {
+        self.expiration = expiration;
+        self
+    }
+
+    /// Set maximum TRX burned for energy (smart contract calls only)
+    pub fn with_fee_limit(mut self, fee_limit: Sun) -> Self {
+        self.fee_limit = fee_limit;
+        self
+    }
+
+    /// Set memo (raw data `data` field)
+    pub fn with_memo(mut self, memo: impl Into<Vec<u8>>) -> Self {
+        self.memo = Some(memo.into());
+        self
+    }
+
+    /// Set id of account permission used to sign (0 for owner, 2.. for active permissions)
+    pub fn with_permission_id(mut self, permission_id: i32) -> Self {
+        self.contract.permission_id = permission_id;
+        self
+    }
+
+    /// Build unsigned transaction, computing raw data bytes and tx id locally
+    pub fn build(self) -> Result<Transaction, Error> {
+        let ref_block_id = self.ref_block_id.ok_or(Error::MissingRefBlock)?;
+        let start = self
+            .timestamp
+            .or(self.ref_block_timestamp)
+            .ok_or(Error::MissingRefBlock)?;
+        let expiration = u64::try_from(self.expiration.as_millis())
+            .ok()
+            .and_then(|window| start.checked_add(window))
+            .ok_or(Error::InvalidExpiration)?;
+        Transaction::from_raw_data(RawTxData {
+            data: self.memo.map(hex::encode),
+            contract: vec![self.contract],
+            expiration,
+            timestamp: self.timestamp,
+            fee_limit: self.fee_limit.as_sun(),
+            ref_block_bytes: ref_block_id.ref_block_bytes().to_vec(),
+            ref_block_hash: ref_block_id.ref_block_hash().to_vec(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use alloc::format;
+
+    #[test]
+    fn test_encode_transfer() {
+        let raw_data: RawTxData = serde_json::from_str(
+            r#"{
+                "contract": [{
+                    "parameter": {
+                        "value": {
+                            "amount": 1000,
+                            "owner_address": "41608f8da72479edc7dd921e4c30bb7e7cddbe722e",
+                            "to_address": "41e9d79cc47518930bc322d9bf7cddd260a0260a8d"
+                        },
+                        "type_url": "type.googleapis.com/protocol.TransferContract"
+                    },
+                    "type": "TransferContract"
+                }],
+                "ref_block_bytes": "5e4b",
+                "ref_block_hash": "47c9dc89341b300d",
+                "expiration": 1591089627000,
+                "timestamp": 1591089567635
+            }"#,
+        )
+        .unwrap();
+        let tx = Transaction::from_raw_data(raw_data).unwrap();
+        assert_eq!(tx.raw_data_hex(), "0a025e4b220847c9dc89341b300d40f8fed3a2a72e5a66080112620a2d747970652e676f6f676c65617069732e636f6d2f70726f746f636f6c2e5472616e73666572436f6e747261637412310a1541608f8da72479edc7dd921e4c30bb7e7cddbe722e121541e9d79cc47518930bc322d9bf7cddd260a0260a8d18e8077093afd0a2a72e");
+        assert_eq!(
+            tx.tx_id,
+            TransactionId::from_raw_data_bytes(&tx.raw_data_bytes)
+        );
+    }
+
+    #[test]
+    fn test_encode_trigger_with_memo() {
+        let raw_data: RawTxData = serde_json::from_str(
+            r#"{
+                "data": "6d656d6f",
+                "contract": [{
+                    "parameter": {
+                        "value": {
+                            "data": "a9059cbb",
+                            "owner_address": "TNPeeaaFB7K9cmo4uQpcU32zGK8G1NYqeL",
+                            "contract_address": "41e9d79cc47518930bc322d9bf7cddd260a0260a8d"
+                        }
+                    },
+                    "type": "TriggerSmartContract"
+                }],
+                "ref_block_bytes": "0001",
+                "ref_block_hash": "0102030405060708",
+                "expiration": 1,
+                "fee_limit": 100000000
+            }"#,
+        )
+        .unwrap();
+        let bytes = raw_data.to_protobuf().unwrap();
+        assert_eq!(hex::encode(bytes), "0a02000122080102030405060708400152046d656d6f5a6d081f12690a31747970652e676f6f676c65617069732e636f6d2f70726f746f636f6c2e54726967676572536d617274436f6e747261637412340a15418840e6c55b9ada326d211d818c34a994aeced808121541e9d79cc47518930bc322d9bf7cddd260a0260a8d2204a9059cbb900180c2d72f");
+    }
+
+    #[test]
+    fn test_decode() {
+        let raw_data: RawTxData = serde_json::from_str(
+            r#"{
+                "data": "6d656d6f",
+                "contract": [{
+                    "parameter": {
+                        "value": {
+                            "owner_address": "418840e6c55b9ada326d211d818c34a994aeced808",
+                            "resource": "ENERGY",
+                            "balance": 1000000,
+                            "receiver_address": "41e9d79cc47518930bc322d9bf7cddd260a0260a8d",
+                            "lock": true,
+                            "lock_period": 86400
+                        },
+                        "type_url": "type.googleapis.com/protocol.DelegateResourceContract"
+                    },
+                    "type": "DelegateResourceContract"
+                }],
+                "ref_block_bytes": "5e4b",
+                "ref_block_hash": "47c9dc89341b300d",
+                "expiration": 1591089627000,
+                "timestamp": 1591089567635,
+                "fee_limit": 0
+            }"#,
+        )
+        .unwrap();
+        let tx = Transaction::from_raw_data(raw_data.clone()).unwrap();
+        assert_eq!(tx.decode_raw_data().unwrap(), raw_data);
+    }
+
+    #[test]
+    fn test_decode_tampered() {
+        let raw_data_hex = "0a025e4b220847c9dc89341b300d40f8fed3a2a72e5a66080112620a2d747970652e676f6f676c65617069732e636f6d2f70726f746f636f6c2e5472616e73666572436f6e747261637412310a1541608f8da72479edc7dd921e4c30bb7e7cddbe722e121541e9d79cc47518930bc322d9bf7cddd260a0260a8d18e8077093afd0a2a72e";
+        let mut tx = Transaction::from_raw_data(
+            RawTxData::from_protobuf(&hex::decode(raw_data_hex).unwrap()).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(tx.raw_data_hex(), raw_data_hex);
+        tx.decode_raw_data().unwrap();
+
+        // unknown field (scripts) appended, tx id recomputed
+        tx.raw_data_bytes
+            .extend_from_slice(&hex_literal::hex!("620100"));
+        tx.tx_id = TransactionId::from_raw_data_bytes(&tx.raw_data_bytes);
+        assert!(matches!(tx.decode_raw_data(), Err(Error::InvalidTxData)));
+
+        tx.tx_id.0[0] ^= 1;
+        assert!(matches!(tx.decode_raw_data(), Err(Error::TxIdMismatch)));
+    }
+
+    #[test]
+    fn test_builder() {
+        let block_id: BlockId = "00000000000a5e4b47c9dc89341b300d0000000000000000000000000000abcd"
+            .parse()
+            .unwrap();
+        let tx = TransactionBuilder::new(crate::contract::TransferContract {
+            owner_address: "41608f8da72479edc7dd921e4c30bb7e7cddbe722e"
+                .parse()
+                .unwrap(),
+            to_address: "41e9d79cc47518930bc322d9bf7cddd260a0260a8d"
+                .parse()
+                .unwrap(),
+            amount: 1000,
+        })
+        .with_ref_block_id(block_id)
+        .with_timestamp(1591089567635)
+        .with_expiration(Duration::from_millis(59365))
+        .build()
+        .unwrap();
+        assert_eq!(tx.raw_data_hex(), "0a025e4b220847c9dc89341b300d40f8fed3a2a72e5a66080112620a2d747970652e676f6f676c65617069732e636f6d2f70726f746f636f6c2e5472616e73666572436f6e747261637412310a1541608f8da72479edc7dd921e4c30bb7e7cddbe722e121541e9d79cc47518930bc322d9bf7cddd260a0260a8d18e8077093afd0a2a72e");
+        assert_eq!(tx.decode_raw_data().unwrap(), tx.raw_data);
+    }
+
+    #[test]
+    fn test_builder_permission_memo() {
+        let tx = TransactionBuilder::new(crate::contract::TriggerSmartContract {
+            owner_address: "TNPeeaaFB7K9cmo4uQpcU32zGK8G1NYqeL".parse().unwrap(),
+            contract_address: "41e9d79cc47518930bc322d9bf7cddd260a0260a8d"
+                .parse()
+                .unwrap(),
+            call_value: 0,
+            data: hex::decode("a9059cbb").unwrap(),
+            call_token_value: 0,
+            token_id: 0,
+        })
+        .with_ref_block_id(BlockId([0x01; 32]))
+        .with_timestamp(0)
+        .with_memo("memo")
+        .with_fee_limit(Sun::new(100_000_000))
+        .with_permission_id(2)
+        .build()
+        .unwrap();
+        assert_eq!(tx.raw_data.expiration, 60_000);
+        assert_eq!(tx.raw_data.data.as_deref(), Some("6d656d6f"));
+        let json = serde_json::to_value(&tx).unwrap();
+        assert_eq!(json["raw_data"]["contract"][0]["Permission_id"], 2);
+        let decoded = tx.decode_raw_data().unwrap();
+        assert_eq!(decoded, tx.raw_data);
+        assert_eq!(decoded.contract[0].permission_id, 2);
+        assert_eq!(tx.permission_id(), 2);
+        let mut owner_tx = tx.clone();
+        owner_tx
+            .signature
+            .push(TronSignature::from_bytes(&[0; 65]).unwrap());
+        owner_tx.set_permission_id(0).unwrap();
+        assert_eq!(owner_tx.permission_id(), 0);
+        assert!(owner_tx.signature.is_empty());
+        assert_ne!(owner_tx.tx_id, tx.tx_id);
+        assert_eq!(owner_tx.decode_raw_data().unwrap(), owner_tx.raw_data);
+        assert_eq!(decoded.fee_limit, 100_000_000);
+        assert!(matches!(
+            TransactionBuilder::new(crate::contract::WithdrawBalanceContract {
+                owner_address: "TNPeeaaFB7K9cmo4uQpcU32zGK8G1NYqeL".parse().unwrap(),
+            })
+            .build(),
+            Err(Error::MissingRefBlock)
+        ));
+        let withdraw = || {
+            TransactionBuilder::new(crate::contract::WithdrawBalanceContract {
+                owner_address: "TNPeeaaFB7K9cmo4uQpcU32zGK8G1NYqeL".parse().unwrap(),
+            })
+            .with_ref_block_id(BlockId([0x01; 32]))
+        };
+        assert!(matches!(
+            withdraw().with_timestamp(u64::MAX).build(),
+            Err(Error::InvalidExpiration)
+        ));
+        assert!(matches!(
+            withdraw()
+                .with_timestamp(1)
+                .with_expiration(Duration::MAX)
+                .build(),
+            Err(Error::InvalidExpiration)
+        ));
+    }
+
+    #[test]
+    fn test_signature() {
+        let hex = "e713bf98011b64960d423ec1b80518ef7708d202d7de37d4f9ca43a273c1fe491b9bb002854eff6f9edeee32420b75a8c080378d74148103dd0229cb8c8482bf01";
+        let signature: TronSignature = hex.parse().unwrap();
+        assert_eq!(signature.recovery_id, 1);
+        assert_eq!(signature.v(), 28);
+        assert_eq!(signature.to_string(), hex);
+        let tronweb: TronSignature = format!("0x{}1c", &hex[..128]).parse().unwrap();
+        assert_eq!(tronweb, signature);
+        assert_eq!(
+            serde_json::to_value(signature).unwrap(),
+            serde_json::Value::String(hex.into())
+        );
+        assert!(format!("{}1f", &hex[..128])
+            .parse::<TronSignature>()
+            .is_err());
+        assert!(hex[..128].parse::<TronSignature>().is_err());
+    }
+
+    #[test]
+    fn test_unsupported_contract() {
+        let contract: Contract = serde_json::from_value(serde_json::json!({
+            "parameter": {
+                "value": { "binding_signature": "00" },
+                "type_url": "type.googleapis.com/protocol.ShieldedTransferContract"
+            },
+            "type": "ShieldedTransferContract"
+        }))
+        .unwrap();
+        assert_eq!(contract.kind.type_name(), "ShieldedTransferContract");
+        assert!(matches!(
+            contract.to_protobuf(),
+            Err(Error::UnsupportedContract)
+        ));
+    }
+}
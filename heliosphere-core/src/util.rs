@@ -4,6 +4,12 @@ use alloc::string::String;
 use core::fmt::{Display, LowerHex};
 use serde::{Deserialize, Deserializer, Serializer};
 
+/// Compute 4-byte method selector, e.g. `transfer(address,uint256)` => `a9059cbb`
+pub fn method_selector(signature: &str) -> [u8; 4] {
+    let hash = alloy_primitives::keccak256(signature.as_bytes());
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
 /// Ser/De number as hex (without 0x prefix)
 pub mod as_hex_number {
 
@@ -110,6 +116,30 @@ pub mod as_hex_address {
     }
 }
 
+/// Ser/De address in TVM log format (hex without 0x41 prefix)
+pub mod as_log_address {
+    use crate::Address;
+
+    use super::*;
+
+    /// Serialize
+    pub fn serialize<S>(address: &Address, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&hex::encode(&address.as_bytes()[1..]))
+    }
+
+    /// Deserialize (see [`Address::parse_lenient`])
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Address, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        Address::parse_lenient(&s).map_err(serde::de::Error::custom)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use alloc::{vec, vec::Vec};
@@ -117,6 +147,14 @@ mod test {
 
     use super::*;
 
+    #[test]
+    fn test_method_selector() {
+        assert_eq!(
+            method_selector("transfer(address,uint256)"),
+            hex_literal::hex!("a9059cbb")
+        );
+    }
+
     #[derive(Serialize, Deserialize)]
     #[repr(transparent)]
     struct Val(#[serde(with = "as_hex_number")] u64);
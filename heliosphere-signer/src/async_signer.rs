@@ -0,0 +1,59 @@
+//! Asynchronous signer for keys that live behind a network call (remote signers, KMS)
+
+use core::{fmt::Debug, future::Future};
+use heliosphere_core::{transaction::Transaction, Address};
+use k256::ecdsa::{RecoveryId, Signature, VerifyingKey};
+
+use crate::signer::{derive_address, to_tron_signature, Signer};
+
+/// Generic asynchronous signer, implemented for every [`Signer`]
+pub trait AsyncSigner: Sync {
+    /// Signer error
+    type Error: Debug + Send + Sync;
+
+    /// Get public key
+    fn public_key(&self) -> impl Future<Output = Result<VerifyingKey, Self::Error>> + Send;
+
+    /// Get Tron address
+    fn address(&self) -> impl Future<Output = Result<Address, Self::Error>> + Send {
+        async { Ok(derive_address(&self.public_key().await?)) }
+    }
+
+    /// Sign hashed value
+    fn sign_prehash(
+        &self,
+        prehash: &[u8],
+    ) -> impl Future<Output = Result<(Signature, RecoveryId), Self::Error>> + Send;
+
+    /// Sign transaction
+    fn sign_transaction(
+        &self,
+        tx: &mut Transaction,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        async {
+            let (signature, rec_id) = self.sign_prehash(&tx.tx_id.0).await?;
+            tx.signature.push(to_tron_signature(&signature, rec_id));
+            Ok(())
+        }
+    }
+}
+
+impl<S: Signer + Sync> AsyncSigner for S {
+    type Error = S::Error;
+
+    async fn public_key(&self) -> Result<VerifyingKey, Self::Error> {
+        Ok(Signer::public_key(self))
+    }
+
+    async fn address(&self) -> Result<Address, Self::Error> {
+        Ok(Signer::address(self))
+    }
+
+    async fn sign_prehash(&self, prehash: &[u8]) -> Result<(Signature, RecoveryId), Self::Error> {
+        Signer::sign_prehash(self, prehash)
+    }
+
+    async fn sign_transaction(&self, tx: &mut Transaction) -> Result<(), Self::Error> {
+        Signer::sign_transaction(self, tx)
+    }
+}
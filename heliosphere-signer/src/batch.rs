@@ -0,0 +1,186 @@
+//! Batch signing and verification, work is split across available CPU cores
+use alloc::vec::Vec;
+use core::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+
+use heliosphere_core::{transaction::Transaction, Address};
+use k256::ecdsa::VerifyingKey;
+
+use crate::{
+    error::SignerError,
+    signer::{SignedTransaction, Signer},
+};
+
+/// Smallest number of transactions worth a thread
+const MIN_CHUNK: usize = 32;
+
+fn chunk_size(len: usize) -> usize {
+    let threads = thread::available_parallelism()
+        .map_or(1, |n| n.get())
+        .min(len.div_ceil(MIN_CHUNK))
+        .max(1);
+    len.div_ceil(threads).max(1)
+}
+
+fn parallel_map<T: Sync, R: Send>(items: &[T], f: impl Fn(&T) -> R + Sync) -> Vec<R> {
+    let chunk = chunk_size(items.len());
+    if chunk >= items.len() {
+        return items.iter().map(f).collect();
+    }
+    let f = &f;
+    thread::scope(|scope| {
+        let workers: Vec<_> = items
+            .chunks(chunk)
+            .map(|chunk| scope.spawn(move || chunk.iter().map(f).collect::<Vec<_>>()))
+            .collect();
+        workers
+            .into_iter()
+            .flat_map(|worker| worker.join().expect("Batch worker panicked"))
+            .collect()
+    })
+}
+
+/// Sign every transaction with `signer`, stops at first error.
+/// On error transactions signed before it (in any thread) keep their signatures.
+pub fn sign_transactions<S>(signer: &S, txs: &mut [Transaction]) -> Result<(), S::Error>
+where
+    S: Signer + Sync,
+    S::Error: Send,
+{
+    let chunk = chunk_size(txs.len());
+    if chunk >= txs.len() {
+        return txs
+            .iter_mut()
+            .try_for_each(|tx| signer.sign_transaction(tx));
+    }
+    let failed = &AtomicBool::new(false);
+    thread::scope(|scope| {
+        let workers: Vec<_> = txs
+            .chunks_mut(chunk)
+            .map(|chunk| {
+                scope.spawn(move || {
+                    for tx in chunk {
+                        if failed.load(Ordering::Relaxed) {
+                            break;
+                        }
+                        signer.sign_transaction(tx).inspect_err(|_| {
+                            failed.store(true, Ordering::Relaxed);
+                        })?;
+                    }
+                    Ok(())
+                })
+            })
+            .collect();
+        workers
+            .into_iter()
+            .try_for_each(|worker| worker.join().expect("Batch worker panicked"))
+    })
+}
+
+/// Recover signers of every transaction (see [`SignedTransaction::recover_signers`])
+pub fn recover_signers(
+    txs: &[Transaction],
+) -> Vec<Result<Vec<(VerifyingKey, Address)>, SignerError>> {
+    parallel_map(txs, |tx| tx.recover_signers())
+}
+
+/// Check that every transaction carries a valid signature of `address`,
+/// returns indices of transactions which don't (including malformed ones)
+pub fn verify_signed_by(txs: &[Transaction], address: &Address) -> Vec<usize> {
+    parallel_map(txs, |tx| tx.verify_signed_by(address).unwrap_or(false))
+        .into_iter()
+        .enumerate()
+        .filter_map(|(i, signed)| (!signed).then_some(i))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use heliosphere_core::{
+        block::BlockId, contract::TransferContract, transaction::TransactionBuilder,
+    };
+
+    use k256::ecdsa::{RecoveryId, Signature};
+
+    use super::*;
+    use crate::keypair::Keypair;
+
+    fn keypair() -> Keypair {
+        Keypair::from_hex_key("b224f69fe10604d71263971ecd9cdc9f2fc59d0e7ddb6c9df2aa8631423d8cb0")
+            .unwrap()
+    }
+
+    fn transactions(address: Address) -> Vec<Transaction> {
+        (1..=200)
+            .map(|amount| {
+                TransactionBuilder::new(TransferContract {
+                    owner_address: address,
+                    to_address: address,
+                    amount,
+                })
+                .with_ref_block_id(BlockId([0x01; 32]))
+                .with_timestamp(1)
+                .build()
+                .unwrap()
+            })
+            .collect()
+    }
+
+    /// Slow signer refusing to sign one prehash
+    struct RefusingSigner(Keypair, [u8; 32]);
+
+    impl Signer for RefusingSigner {
+        type Error = SignerError;
+
+        fn public_key(&self) -> VerifyingKey {
+            Signer::public_key(&self.0)
+        }
+
+        fn sign_prehash(&self, prehash: &[u8]) -> Result<(Signature, RecoveryId), SignerError> {
+            if prehash == self.1 {
+                return Err(SignerError::InvalidSignature);
+            }
+            thread::sleep(std::time::Duration::from_millis(5));
+            Ok(Signer::sign_prehash(&self.0, prehash).unwrap())
+        }
+    }
+
+    #[test]
+    fn test_batch() {
+        let keypair = keypair();
+        let address = Signer::address(&keypair);
+        let mut txs = transactions(address);
+        let mut expected = txs.clone();
+        sign_transactions(&keypair, &mut txs).unwrap();
+        for tx in &mut expected {
+            keypair.sign_transaction(tx).unwrap();
+        }
+        assert_eq!(
+            txs.iter().map(|tx| &tx.signature).collect::<Vec<_>>(),
+            expected.iter().map(|tx| &tx.signature).collect::<Vec<_>>()
+        );
+
+        assert!(verify_signed_by(&txs, &address).is_empty());
+        txs[7].signature.clear();
+        txs[150].signature[0].s[31] ^= 1;
+        txs[199].tx_id.0[0] ^= 1;
+        assert_eq!(verify_signed_by(&txs, &address), [7, 150, 199]);
+        let signers = recover_signers(&txs);
+        assert_eq!(signers[0].as_ref().unwrap()[0].1, address);
+        assert!(signers[7].as_ref().unwrap().is_empty());
+        assert!(matches!(signers[199], Err(SignerError::TxIdMismatch)));
+    }
+
+    #[test]
+    fn test_batch_abort() {
+        let mut txs = transactions(Signer::address(&keypair()));
+        let signer = RefusingSigner(keypair(), txs[0].tx_id.0);
+        assert!(matches!(
+            sign_transactions(&signer, &mut txs),
+            Err(SignerError::InvalidSignature)
+        ));
+        // other threads stop instead of signing their whole chunks
+        let signed = txs.iter().filter(|tx| !tx.signature.is_empty()).count();
+        assert!(signed < txs.len() / 2, "{}", signed);
+    }
+}
@@ -0,0 +1,59 @@
+//! Vanity TRON address generator
+//!
+//! `KEYSTORE_PASSWORD=... tron-vanity [--threads N] [--timeout SECS] [--out DIR] <prefix:T...|suffix:...|regex:...>`
+//!
+//! Found key is saved as encrypted keystore into `--out` directory (current directory by default).
+use std::{
+    sync::atomic::{AtomicBool, Ordering},
+    time::{Duration, Instant},
+};
+
+use heliosphere_signer::{
+    keystore::KeystoreDir,
+    vanity::{Pattern, VanitySearch},
+};
+
+const USAGE: &str = "usage: tron-vanity [--threads N] [--timeout SECS] [--out DIR] <prefix:T...|suffix:...|regex:...>";
+
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut args = std::env::args().skip(1);
+    let (mut threads, mut timeout, mut out, mut pattern) = (None, None, ".".to_owned(), None);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--threads" => threads = Some(args.next().ok_or(USAGE)?.parse()?),
+            "--timeout" => timeout = Some(Duration::from_secs(args.next().ok_or(USAGE)?.parse()?)),
+            "--out" => out = args.next().ok_or(USAGE)?,
+            _ if pattern.is_none() => pattern = Some(arg),
+            _ => return Err(USAGE.into()),
+        }
+    }
+    let pattern: Pattern = pattern
+        .ok_or(USAGE)?
+        .parse()
+        .map_err(|_| "pattern can't match any address")?;
+    let password = std::env::var("KEYSTORE_PASSWORD")
+        .map_err(|_| "environment variable KEYSTORE_PASSWORD is not set")?;
+    let mut keystore = KeystoreDir::open(out)?;
+
+    let mut search = VanitySearch::new(pattern);
+    if let Some(threads) = threads {
+        search = search.with_threads(threads);
+    }
+    let start = Instant::now();
+    let cancel = AtomicBool::new(false);
+    let found = search.run(&cancel, |progress| {
+        let mut line = format!("{} keys, {:.0} keys/s", progress.attempts, progress.rate());
+        if let (Some(p), Some(t)) = (progress.probability(), progress.expected_time()) {
+            line += &format!(", {:.1}% chance so far, {:.0?} expected", p * 100.0, t);
+        }
+        eprintln!("{}", line);
+        if timeout.is_some_and(|timeout| start.elapsed() >= timeout) {
+            cancel.store(true, Ordering::Relaxed);
+        }
+    });
+    let keypair = found.ok_or("timed out")?;
+    let address = keystore.insert(&keypair, &password)?;
+    println!("{}", address);
+    eprintln!("saved to {}", keystore.path_of(&address).unwrap().display());
+    Ok(())
+}
@@ -13,4 +13,52 @@ pub enum SignerError {
     /// Invalid key bytes
     #[cfg_attr(feature = "std", error("InvalidKey"))]
     InvalidKey,
+    /// Invalid BIP39 mnemonic phrase or word count
+    #[cfg_attr(feature = "std", error("InvalidMnemonic"))]
+    InvalidMnemonic,
+    /// Invalid BIP32 derivation path
+    #[cfg_attr(feature = "std", error("InvalidDerivationPath"))]
+    InvalidDerivationPath,
+    /// Malformed or unsupported keystore
+    #[cfg_attr(feature = "std", error("InvalidKeystore"))]
+    InvalidKeystore,
+    /// Keystore password is wrong (MAC mismatch)
+    #[cfg_attr(feature = "std", error("InvalidPassword"))]
+    InvalidPassword,
+    /// Account is not in keystore
+    #[cfg_attr(feature = "std", error("AccountNotFound"))]
+    AccountNotFound(heliosphere_core::Address),
+    /// Keystore file read/write failed
+    #[cfg_attr(feature = "std", error("Io: {0}"))]
+    Io(alloc::string::String),
+    /// Vanity pattern can't match any address
+    #[cfg_attr(feature = "std", error("InvalidVanityPattern"))]
+    InvalidVanityPattern,
+    /// Key is not in PKCS#11 token
+    #[cfg_attr(feature = "std", error("KeyNotFound"))]
+    KeyNotFound,
+    /// PKCS#11 module or token failure
+    #[cfg_attr(feature = "std", error("Pkcs11: {0}"))]
+    Pkcs11(alloc::string::String),
+    /// Invalid signature bytes or public key can't be recovered
+    #[cfg_attr(feature = "std", error("InvalidSignature"))]
+    InvalidSignature,
+    /// Malformed TIP-712 typed data
+    #[cfg_attr(feature = "std", error("InvalidTypedData"))]
+    InvalidTypedData,
+    /// Signature was produced by another address
+    #[cfg_attr(feature = "std", error("UnexpectedSigner"))]
+    UnexpectedSigner(heliosphere_core::Address),
+    /// Transaction signed by address which is not a key of the permission
+    #[cfg_attr(feature = "std", error("SignerNotInPermission"))]
+    SignerNotInPermission(heliosphere_core::Address),
+    /// Transaction signed twice by the same address
+    #[cfg_attr(feature = "std", error("DuplicateSigner"))]
+    DuplicateSigner(heliosphere_core::Address),
+    /// Transaction permission id does not match permission
+    #[cfg_attr(feature = "std", error("PermissionMismatch"))]
+    PermissionMismatch,
+    /// Transactions have different ids
+    #[cfg_attr(feature = "std", error("TxIdMismatch"))]
+    TxIdMismatch,
 }
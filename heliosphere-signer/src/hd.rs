@@ -0,0 +1,243 @@
+//! BIP39 mnemonics and BIP32/BIP44 hierarchical deterministic key derivation
+
+use alloc::{format, vec::Vec};
+use core::ops::Range;
+
+pub use bip32::{ChildNumber, DerivationPath, Prefix, XPrv, XPub};
+pub use bip39::{Language, Mnemonic};
+use heliosphere_core::Address;
+use hmac::{Hmac, Mac};
+use k256::{
+    ecdsa::VerifyingKey,
+    elliptic_curve::{BatchNormalize, PrimeField},
+    ProjectivePoint, Scalar,
+};
+use rand_core::{CryptoRng, RngCore};
+use sha2::Sha512;
+use zeroize::Zeroizing;
+
+use crate::{error::SignerError, keypair::Keypair, signer::derive_address};
+
+/// SLIP-44 coin type of TRON
+pub const TRON_COIN_TYPE: u32 = 195;
+
+/// Generate new random mnemonic (12, 15, 18, 21 or 24 words)
+pub fn generate_mnemonic<R>(
+    rng: &mut R,
+    language: Language,
+    word_count: usize,
+) -> Result<Mnemonic, SignerError>
+where
+    R: RngCore + CryptoRng,
+{
+    Mnemonic::generate_in_with(rng, language, word_count).map_err(|_| SignerError::InvalidMnemonic)
+}
+
+/// Parse and validate mnemonic phrase (word list is detected automatically)
+pub fn parse_mnemonic(phrase: &str) -> Result<Mnemonic, SignerError> {
+    Mnemonic::parse(phrase).map_err(|_| SignerError::InvalidMnemonic)
+}
+
+/// TRON BIP44 derivation path `m/44'/195'/account'/0/index`
+pub fn tron_derivation_path(account: u32, index: u32) -> Result<DerivationPath, SignerError> {
+    format!("m/44'/{}'/{}'/0/{}", TRON_COIN_TYPE, account, index)
+        .parse()
+        .map_err(|_| SignerError::InvalidDerivationPath)
+}
+
+impl Keypair {
+    /// Derive keypair from BIP39 seed along derivation path
+    pub fn from_seed(seed: &[u8], path: &DerivationPath) -> Result<Self, SignerError> {
+        let xprv = XPrv::derive_from_path(seed, path).map_err(|_| SignerError::InvalidKey)?;
+        Ok(Self::from_signing_key(xprv.private_key().clone()))
+    }
+
+    /// Derive keypair from mnemonic (with optional passphrase, empty if unused) along derivation path
+    pub fn from_mnemonic(
+        mnemonic: &Mnemonic,
+        passphrase: &str,
+        path: &DerivationPath,
+    ) -> Result<Self, SignerError> {
+        Self::from_seed(&Zeroizing::new(mnemonic.to_seed(passphrase))[..], path)
+    }
+
+    /// Derive `index`-th address of `account` (`m/44'/195'/account'/0/index`)
+    pub fn from_mnemonic_index(
+        mnemonic: &Mnemonic,
+        passphrase: &str,
+        account: u32,
+        index: u32,
+    ) -> Result<Self, SignerError> {
+        Self::from_mnemonic(mnemonic, passphrase, &tron_derivation_path(account, index)?)
+    }
+
+    /// Derive account the way TronLink and Ledger Live do (`m/44'/195'/account'/0/0`)
+    pub fn from_mnemonic_account(
+        mnemonic: &Mnemonic,
+        passphrase: &str,
+        account: u32,
+    ) -> Result<Self, SignerError> {
+        Self::from_mnemonic_index(mnemonic, passphrase, account, 0)
+    }
+}
+
+/// Extended public key of TRON account external chain (`m/44'/195'/account'/0`),
+/// to be exported to watch-only hosts
+pub fn account_xpub(
+    mnemonic: &Mnemonic,
+    passphrase: &str,
+    account: u32,
+) -> Result<XPub, SignerError> {
+    let path = format!("m/44'/{}'/{}'/0", TRON_COIN_TYPE, account)
+        .parse()
+        .map_err(|_| SignerError::InvalidDerivationPath)?;
+    let seed = Zeroizing::new(mnemonic.to_seed(passphrase));
+    let xprv = XPrv::derive_from_path(&seed[..], &path).map_err(|_| SignerError::InvalidKey)?;
+    Ok(xprv.public_key())
+}
+
+/// Parse extended public key (`xpub...`)
+pub fn parse_xpub(xpub: &str) -> Result<XPub, SignerError> {
+    xpub.parse().map_err(|_| SignerError::InvalidKey)
+}
+
+/// Watch-only derivation of non-hardened child keys and addresses from extended public key.
+///
+/// Parent key and chain code are preprocessed once, so deriving an address costs a single
+/// HMAC-SHA512 and one point multiplication.
+#[derive(Clone)]
+pub struct XPubDeriver {
+    hmac: Hmac<Sha512>,
+    parent: ProjectivePoint,
+}
+
+impl XPubDeriver {
+    /// Create deriver for children of `xpub`
+    pub fn new(xpub: &XPub) -> Self {
+        let mut hmac =
+            Hmac::<Sha512>::new_from_slice(&xpub.attrs().chain_code).expect("Any key size");
+        hmac.update(&xpub.to_bytes());
+        Self {
+            hmac,
+            parent: xpub.public_key().as_affine().into(),
+        }
+    }
+
+    fn derive_point(&self, index: u32) -> Result<ProjectivePoint, SignerError> {
+        if index >= ChildNumber::HARDENED_FLAG {
+            return Err(SignerError::InvalidDerivationPath);
+        }
+        let mut hmac = self.hmac.clone();
+        hmac.update(&index.to_be_bytes());
+        let result = hmac.finalize().into_bytes();
+        let tweak: [u8; 32] = result[..32].try_into().expect("Always 32 bytes");
+        let tweak: Option<Scalar> = Scalar::from_repr(tweak.into()).into();
+        // Invalid tweak has probability lower than 1 in 2^127 (see BIP32)
+        let child =
+            self.parent + ProjectivePoint::GENERATOR * tweak.ok_or(SignerError::InvalidKey)?;
+        if child == ProjectivePoint::IDENTITY {
+            return Err(SignerError::InvalidKey);
+        }
+        Ok(child)
+    }
+
+    /// Derive public key of child `index`
+    pub fn derive_public_key(&self, index: u32) -> Result<VerifyingKey, SignerError> {
+        VerifyingKey::from_affine(self.derive_point(index)?.to_affine())
+            .map_err(|_| SignerError::InvalidKey)
+    }
+
+    /// Derive address of child `index`
+    pub fn derive_address(&self, index: u32) -> Result<Address, SignerError> {
+        Ok(derive_address(&self.derive_public_key(index)?))
+    }
+
+    /// Derive addresses of a range of children (uses batch point normalization).
+    /// For millions of indexes split the range and derive chunks in parallel, deriver is `Send + Sync`.
+    pub fn derive_addresses(&self, indexes: Range<u32>) -> Result<Vec<Address>, SignerError> {
+        let points = indexes
+            .map(|index| self.derive_point(index))
+            .collect::<Result<Vec<_>, _>>()?;
+        <ProjectivePoint as BatchNormalize<[ProjectivePoint]>>::batch_normalize(&points)
+            .into_iter()
+            .map(|point| {
+                VerifyingKey::from_affine(point)
+                    .map(|key| derive_address(&key))
+                    .map_err(|_| SignerError::InvalidKey)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::signer::Signer;
+
+    const PHRASE: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    #[test]
+    fn test_seed() {
+        let mnemonic = parse_mnemonic(PHRASE).unwrap();
+        assert_eq!(hex::encode(mnemonic.to_seed("TREZOR")), "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04");
+        assert!(parse_mnemonic("abandon abandon abandon").is_err());
+        assert!(parse_mnemonic(&PHRASE.replace("about", "abandon")).is_err());
+    }
+
+    #[test]
+    fn test_derivation() {
+        let mnemonic = parse_mnemonic(PHRASE).unwrap();
+        let keypair = Keypair::from_mnemonic_account(&mnemonic, "", 0).unwrap();
+        assert_eq!(
+            keypair.address().as_base58(),
+            "TUEZSdKsoDHQMeZwihtdoBiN46zxhGWYdH"
+        );
+        assert_eq!(
+            Keypair::from_mnemonic(&mnemonic, "", &"m/44'/195'/0'/0/0".parse().unwrap())
+                .unwrap()
+                .address(),
+            keypair.address()
+        );
+        assert_ne!(
+            Keypair::from_mnemonic_index(&mnemonic, "", 0, 1)
+                .unwrap()
+                .address(),
+            keypair.address()
+        );
+        assert!(tron_derivation_path(1 << 31, 0).is_err());
+    }
+
+    #[test]
+    fn test_generate() {
+        let mnemonic = generate_mnemonic(&mut rand_core::OsRng, Language::Japanese, 24).unwrap();
+        assert_eq!(mnemonic.word_count(), 24);
+        let parsed = parse_mnemonic(&alloc::string::ToString::to_string(&mnemonic)).unwrap();
+        assert_eq!(parsed.language(), Language::Japanese);
+        assert!(generate_mnemonic(&mut rand_core::OsRng, Language::English, 13).is_err());
+    }
+
+    #[test]
+    fn test_xpub_derivation() {
+        let mnemonic = parse_mnemonic(PHRASE).unwrap();
+        let xpub = account_xpub(&mnemonic, "", 0).unwrap();
+        let xpub = parse_xpub(&xpub.to_string(Prefix::XPUB)).unwrap();
+        let deriver = XPubDeriver::new(&xpub);
+        assert_eq!(
+            deriver.derive_address(0).unwrap().as_base58(),
+            "TUEZSdKsoDHQMeZwihtdoBiN46zxhGWYdH"
+        );
+        let addresses = deriver.derive_addresses(0..20).unwrap();
+        for (index, address) in addresses.into_iter().enumerate() {
+            let keypair = Keypair::from_mnemonic_index(&mnemonic, "", 0, index as u32).unwrap();
+            assert_eq!(address, keypair.address());
+            let child = xpub
+                .derive_child(ChildNumber::new(index as u32, false).unwrap())
+                .unwrap();
+            assert_eq!(
+                &deriver.derive_public_key(index as u32).unwrap(),
+                child.public_key()
+            );
+        }
+        assert!(deriver.derive_address(ChildNumber::HARDENED_FLAG).is_err());
+    }
+}
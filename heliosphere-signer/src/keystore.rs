@@ -0,0 +1,609 @@
+//! Encrypted keystore (Web3 Secret Storage v3), compatible with TronLink, wallet-cli and geth
+
+use aes::cipher::{KeyIvInit, StreamCipher};
+use alloc::{
+    collections::BTreeMap,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use heliosphere_core::Address;
+use k256::ecdsa::SigningKey;
+use rand_core::{CryptoRng, OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+use sha3::{Digest, Keccak256};
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+};
+use zeroize::Zeroizing;
+
+use crate::{error::SignerError, keypair::Keypair, signer::Signer};
+
+type Aes128Ctr = ctr::Ctr128BE<aes::Aes128>;
+
+/// Maximum scrypt `log_n` (n = 2^20, 1 GiB of memory with r = 8)
+const MAX_SCRYPT_LOG_N: u8 = 20;
+/// Maximum scrypt block size
+const MAX_SCRYPT_R: u32 = 8;
+/// Maximum scrypt parallelization
+const MAX_SCRYPT_P: u32 = 16;
+/// Maximum PBKDF2 iteration count
+const MAX_PBKDF2_C: u32 = 10_000_000;
+
+/// Key derivation function used to encrypt keystore
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kdf {
+    /// scrypt with cost `2^log_n`, at most `log_n = 20`, `r = 8` and `p = 16` are accepted
+    Scrypt {
+        /// log2 of CPU/memory cost
+        log_n: u8,
+        /// Block size
+        r: u32,
+        /// Parallelization
+        p: u32,
+    },
+    /// PBKDF2-HMAC-SHA256, at most 10 million iterations are accepted
+    Pbkdf2 {
+        /// Iteration count
+        c: u32,
+    },
+}
+
+impl Default for Kdf {
+    /// geth / wallet-cli standard scrypt parameters (n = 262144, r = 8, p = 1)
+    fn default() -> Self {
+        Self::Scrypt {
+            log_n: 18,
+            r: 8,
+            p: 1,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct KeystoreJson {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    address: Option<String>,
+    #[serde(alias = "Crypto")]
+    crypto: CryptoJson,
+    #[serde(default)]
+    id: String,
+    version: u32,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CryptoJson {
+    cipher: String,
+    cipherparams: CipherParamsJson,
+    ciphertext: String,
+    kdf: String,
+    kdfparams: KdfParamsJson,
+    mac: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CipherParamsJson {
+    iv: String,
+}
+
+#[derive(Serialize, Deserialize)]
+struct KdfParamsJson {
+    dklen: usize,
+    salt: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    n: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    r: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    p: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    c: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    prf: Option<String>,
+}
+
+/// Derive 32-byte key, rejecting parameters above limits (keystore files are untrusted input)
+fn derive_key(kdf: Kdf, password: &[u8], salt: &[u8]) -> Result<Zeroizing<[u8; 32]>, SignerError> {
+    let mut key = Zeroizing::new([0; 32]);
+    match kdf {
+        Kdf::Scrypt { log_n, r, p } => {
+            if log_n > MAX_SCRYPT_LOG_N || r > MAX_SCRYPT_R || p > MAX_SCRYPT_P {
+                return Err(SignerError::InvalidKeystore);
+            }
+            let params =
+                scrypt::Params::new(log_n, r, p).map_err(|_| SignerError::InvalidKeystore)?;
+            scrypt::scrypt(password, salt, &params, &mut *key)
+                .map_err(|_| SignerError::InvalidKeystore)?;
+        }
+        Kdf::Pbkdf2 { c } => {
+            if c == 0 || c > MAX_PBKDF2_C {
+                return Err(SignerError::InvalidKeystore);
+            }
+            pbkdf2::pbkdf2_hmac::<sha2::Sha256>(password, salt, c, &mut *key);
+        }
+    }
+    Ok(key)
+}
+
+fn mac(key: &[u8; 32], ciphertext: &[u8]) -> [u8; 32] {
+    let mut digest = Keccak256::new();
+    digest.update(&key[16..32]);
+    digest.update(ciphertext);
+    digest.finalize().into()
+}
+
+fn parse_address(address: &str) -> Result<Address, SignerError> {
+    let address = address.strip_prefix("0x").unwrap_or(address);
+    if address.len() == 40 {
+        format!("41{}", address).parse()
+    } else {
+        address.parse()
+    }
+    .map_err(|_| SignerError::InvalidKeystore)
+}
+
+/// Read address of keystore without decrypting it (None if keystore does not store address)
+pub fn keystore_address(json: &str) -> Result<Option<Address>, SignerError> {
+    let keystore: KeystoreJson =
+        serde_json::from_str(json).map_err(|_| SignerError::InvalidKeystore)?;
+    keystore.address.as_deref().map(parse_address).transpose()
+}
+
+impl Keypair {
+    /// Encrypt private key into keystore JSON with default (scrypt) parameters
+    pub fn to_keystore(&self, password: &str) -> Result<String, SignerError> {
+        self.to_keystore_with(&mut OsRng, password, Kdf::default())
+    }
+
+    /// Encrypt private key into keystore JSON with custom RNG and key derivation function
+    pub fn to_keystore_with<R>(
+        &self,
+        rng: &mut R,
+        password: &str,
+        kdf: Kdf,
+    ) -> Result<String, SignerError>
+    where
+        R: RngCore + CryptoRng,
+    {
+        let mut salt = [0; 32];
+        let mut iv = [0; 16];
+        let mut id = [0; 16];
+        rng.fill_bytes(&mut salt);
+        rng.fill_bytes(&mut iv);
+        rng.fill_bytes(&mut id);
+        // uuid v4
+        id[6] = (id[6] & 0x0f) | 0x40;
+        id[8] = (id[8] & 0x3f) | 0x80;
+
+        let key = derive_key(kdf, password.as_bytes(), &salt)?;
+        let mut ciphertext: Vec<u8> =
+            Zeroizing::new(<[u8; 32]>::from(self.signing_key().to_bytes())).to_vec();
+        Aes128Ctr::new(key[..16].into(), &iv.into()).apply_keystream(&mut ciphertext);
+
+        let kdfparams = match kdf {
+            Kdf::Scrypt { log_n, r, p } => KdfParamsJson {
+                dklen: 32,
+                salt: hex::encode(salt),
+                n: Some(1 << log_n),
+                r: Some(r),
+                p: Some(p),
+                c: None,
+                prf: None,
+            },
+            Kdf::Pbkdf2 { c } => KdfParamsJson {
+                dklen: 32,
+                salt: hex::encode(salt),
+                n: None,
+                r: None,
+                p: None,
+                c: Some(c),
+                prf: Some("hmac-sha256".into()),
+            },
+        };
+        let keystore = KeystoreJson {
+            address: Some(self.address().as_base58()),
+            crypto: CryptoJson {
+                cipher: "aes-128-ctr".into(),
+                cipherparams: CipherParamsJson {
+                    iv: hex::encode(iv),
+                },
+                mac: hex::encode(mac(&key, &ciphertext)),
+                ciphertext: hex::encode(ciphertext),
+                kdf: match kdf {
+                    Kdf::Scrypt { .. } => "scrypt",
+                    Kdf::Pbkdf2 { .. } => "pbkdf2",
+                }
+                .into(),
+                kdfparams,
+            },
+            id: format!(
+                "{}-{}-{}-{}-{}",
+                hex::encode(&id[..4]),
+                hex::encode(&id[4..6]),
+                hex::encode(&id[6..8]),
+                hex::encode(&id[8..10]),
+                hex::encode(&id[10..])
+            ),
+            version: 3,
+        };
+        serde_json::to_string(&keystore).map_err(|_| SignerError::InvalidKeystore)
+    }
+
+    /// Decrypt keystore JSON (address, if stored, may be base58, 41-prefixed or 20-byte hex)
+    pub fn from_keystore(json: &str, password: &str) -> Result<Self, SignerError> {
+        let keystore: KeystoreJson =
+            serde_json::from_str(json).map_err(|_| SignerError::InvalidKeystore)?;
+        let crypto = &keystore.crypto;
+        if keystore.version != 3 || crypto.cipher != "aes-128-ctr" {
+            return Err(SignerError::InvalidKeystore);
+        }
+        let params = &crypto.kdfparams;
+        let kdf = match crypto.kdf.as_str() {
+            "scrypt" => {
+                let n = params.n.ok_or(SignerError::InvalidKeystore)?;
+                if !n.is_power_of_two() || n > 1 << MAX_SCRYPT_LOG_N {
+                    return Err(SignerError::InvalidKeystore);
+                }
+                Kdf::Scrypt {
+                    log_n: n.trailing_zeros() as u8,
+                    r: params.r.ok_or(SignerError::InvalidKeystore)?,
+                    p: params.p.ok_or(SignerError::InvalidKeystore)?,
+                }
+            }
+            "pbkdf2" if params.prf.as_deref().unwrap_or("hmac-sha256") == "hmac-sha256" => {
+                Kdf::Pbkdf2 {
+                    c: params.c.ok_or(SignerError::InvalidKeystore)?,
+                }
+            }
+            _ => return Err(SignerError::InvalidKeystore),
+        };
+        if params.dklen != 32 {
+            return Err(SignerError::InvalidKeystore);
+        }
+        let decode = |s: &str| hex::decode(s).map_err(|_| SignerError::InvalidKeystore);
+        let salt = decode(&params.salt)?;
+        let iv: [u8; 16] = decode(&crypto.cipherparams.iv)?
+            .try_into()
+            .map_err(|_| SignerError::InvalidKeystore)?;
+        let mut ciphertext = Zeroizing::new(decode(&crypto.ciphertext)?);
+
+        let key = derive_key(kdf, password.as_bytes(), &salt)?;
+        if mac(&key, &ciphertext)[..] != decode(&crypto.mac)?[..] {
+            return Err(SignerError::InvalidPassword);
+        }
+        Aes128Ctr::new(key[..16].into(), &iv.into()).apply_keystream(&mut ciphertext);
+        let signing_key =
+            SigningKey::from_slice(&ciphertext).map_err(|_| SignerError::InvalidKey)?;
+        let keypair = Self::from_signing_key(signing_key);
+        if let Some(address) = keystore.address.as_deref() {
+            if parse_address(address)? != keypair.address() {
+                return Err(SignerError::InvalidKeystore);
+            }
+        }
+        Ok(keypair)
+    }
+}
+
+/// Directory of keystore files indexed by address
+#[derive(Debug, Clone)]
+pub struct KeystoreDir {
+    path: PathBuf,
+    accounts: BTreeMap<Address, PathBuf>,
+}
+
+impl KeystoreDir {
+    /// Open (and create if missing) keystore directory, indexing files which store address.
+    /// Files that are not keystores are ignored.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, SignerError> {
+        let path = path.as_ref().to_path_buf();
+        std::fs::create_dir_all(&path)?;
+        let mut accounts = BTreeMap::new();
+        for entry in std::fs::read_dir(&path)? {
+            let entry = entry?;
+            if !entry.file_type()?.is_file()
+                || entry.path().extension().is_some_and(|ext| ext == "tmp")
+            {
+                continue;
+            }
+            let Ok(json) = std::fs::read_to_string(entry.path()) else {
+                continue;
+            };
+            if let Ok(Some(address)) = keystore_address(&json) {
+                accounts.insert(address, entry.path());
+            }
+        }
+        Ok(Self { path, accounts })
+    }
+
+    /// Addresses of stored accounts
+    pub fn addresses(&self) -> impl Iterator<Item = &Address> {
+        self.accounts.keys()
+    }
+
+    /// Whether account is stored
+    pub fn contains(&self, address: &Address) -> bool {
+        self.accounts.contains_key(address)
+    }
+
+    /// Path of account keystore file
+    pub fn path_of(&self, address: &Address) -> Option<&Path> {
+        self.accounts.get(address).map(PathBuf::as_path)
+    }
+
+    /// Encrypt and store keypair (replacing existing keystore of the same address)
+    pub fn insert(&mut self, keypair: &Keypair, password: &str) -> Result<Address, SignerError> {
+        self.insert_with(keypair, password, Kdf::default())
+    }
+
+    /// Encrypt and store keypair using custom key derivation function
+    pub fn insert_with(
+        &mut self,
+        keypair: &Keypair,
+        password: &str,
+        kdf: Kdf,
+    ) -> Result<Address, SignerError> {
+        let address = keypair.address();
+        let json = keypair.to_keystore_with(&mut OsRng, password, kdf)?;
+        let path = self
+            .accounts
+            .get(&address)
+            .cloned()
+            .unwrap_or_else(|| self.path.join(format!("{}.json", address)));
+        write_atomic(&path, json.as_bytes())?;
+        self.accounts.insert(address, path);
+        Ok(address)
+    }
+
+    /// Decrypt account keystore
+    pub fn load(&self, address: &Address, password: &str) -> Result<Keypair, SignerError> {
+        let path = self
+            .accounts
+            .get(address)
+            .ok_or(SignerError::AccountNotFound(*address))?;
+        Keypair::from_keystore(&std::fs::read_to_string(path)?, password)
+    }
+
+    /// Delete account keystore file
+    pub fn remove(&mut self, address: &Address) -> Result<(), SignerError> {
+        let path = self
+            .accounts
+            .remove(address)
+            .ok_or(SignerError::AccountNotFound(*address))?;
+        std::fs::remove_file(path)?;
+        Ok(())
+    }
+}
+
+/// Write owner-only readable file via temporary file and rename, so existing file is never partially overwritten
+fn write_atomic(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    let mut tmp_path = std::ffi::OsString::from(path.as_os_str());
+    tmp_path.push(".tmp");
+    let tmp_path = PathBuf::from(tmp_path);
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    let result = (|| {
+        let mut file = options.open(&tmp_path)?;
+        file.write_all(contents)?;
+        file.sync_all()?;
+        std::fs::rename(&tmp_path, path)
+    })();
+    if result.is_err() {
+        let _ = std::fs::remove_file(&tmp_path);
+    }
+    result?;
+    #[cfg(unix)]
+    if let Some(dir) = path.parent() {
+        std::fs::File::open(dir)?.sync_all()?;
+    }
+    Ok(())
+}
+
+impl From<std::io::Error> for SignerError {
+    fn from(e: std::io::Error) -> Self {
+        SignerError::Io(e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const KEY: &str = "7a28b5ba57c53603b0b07b56bba752f7784bf506fa95edc395f5cf6c7514fe9d";
+
+    #[test]
+    fn test_decrypt_pbkdf2() {
+        let json = r#"{
+            "crypto": {
+                "cipher": "aes-128-ctr",
+                "cipherparams": { "iv": "6087dab2f9fdbbfaddc31a909735c1e6" },
+                "ciphertext": "5318b4d5bcd28de64ee5559e671353e16f075ecae9f99c7a79a38af5f869aa46",
+                "kdf": "pbkdf2",
+                "kdfparams": {
+                    "c": 262144,
+                    "dklen": 32,
+                    "prf": "hmac-sha256",
+                    "salt": "ae3cd4e7013836a3df6bd7241b12db061dbe2c6785853cce422d148a624ce0bd"
+                },
+                "mac": "517ead924a9d0dc3124507e3393d175ce3ff7c1e96529c6c555ce9e51205e9b2"
+            },
+            "id": "3198bc9c-6672-5ab3-d995-4942343ae5b6",
+            "version": 3
+        }"#;
+        let keypair = Keypair::from_keystore(json, "testpassword").unwrap();
+        assert_eq!(hex::encode(keypair.signing_key().to_bytes()), KEY);
+        assert!(matches!(
+            Keypair::from_keystore(json, "wrong"),
+            Err(SignerError::InvalidPassword)
+        ));
+    }
+
+    #[test]
+    fn test_decrypt_scrypt() {
+        let json = r#"{
+            "crypto": {
+                "cipher": "aes-128-ctr",
+                "cipherparams": { "iv": "83dbcc02d8ccb40e466191a123791e0e" },
+                "ciphertext": "d172bf743a674da9cdad04534d56926ef8358534d458fffccd4e6ad2fbde479c",
+                "kdf": "scrypt",
+                "kdfparams": {
+                    "dklen": 32,
+                    "n": 262144,
+                    "p": 8,
+                    "r": 1,
+                    "salt": "ab0c7876052600dd703518d6fc3fe8984592145b591fc8fb5c6d43190334ba19"
+                },
+                "mac": "2103ac29920d71da29f15d75b4a16dbe95cfd7ff8faea1056c33131d846e3097"
+            },
+            "id": "3198bc9c-6672-5ab3-d995-4942343ae5b6",
+            "version": 3
+        }"#;
+        let keypair = Keypair::from_keystore(json, "testpassword").unwrap();
+        assert_eq!(hex::encode(keypair.signing_key().to_bytes()), KEY);
+    }
+
+    #[test]
+    fn test_kdf_limits() {
+        let keypair = Keypair::from_hex_key(KEY).unwrap();
+        let json = keypair
+            .to_keystore_with(&mut OsRng, "secret", Kdf::Pbkdf2 { c: 1024 })
+            .unwrap();
+        let mut value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        for (param, invalid) in [
+            ("dklen", 33),
+            ("dklen", 1 << 40),
+            ("c", 0),
+            ("c", MAX_PBKDF2_C as u64 + 1),
+        ] {
+            let mut value = value.clone();
+            value["crypto"]["kdfparams"][param] = invalid.into();
+            assert!(
+                matches!(
+                    Keypair::from_keystore(&value.to_string(), "secret"),
+                    Err(SignerError::InvalidKeystore)
+                ),
+                "{} = {}",
+                param,
+                invalid
+            );
+        }
+
+        value["crypto"]["kdf"] = "scrypt".into();
+        value["crypto"]["kdfparams"] = serde_json::json!({
+            "dklen": 32,
+            "n": 1024,
+            "r": 8,
+            "p": 1,
+            "salt": value["crypto"]["kdfparams"]["salt"],
+        });
+        for (param, invalid) in [("n", 1u64 << 21), ("n", 1000), ("r", 9), ("p", 17)] {
+            let mut value = value.clone();
+            value["crypto"]["kdfparams"][param] = invalid.into();
+            assert!(
+                matches!(
+                    Keypair::from_keystore(&value.to_string(), "secret"),
+                    Err(SignerError::InvalidKeystore)
+                ),
+                "{} = {}",
+                param,
+                invalid
+            );
+        }
+        // within limits, fails on MAC only
+        assert!(matches!(
+            Keypair::from_keystore(&value.to_string(), "secret"),
+            Err(SignerError::InvalidPassword)
+        ));
+        assert!(keypair
+            .to_keystore_with(
+                &mut OsRng,
+                "secret",
+                Kdf::Scrypt {
+                    log_n: 21,
+                    r: 8,
+                    p: 1
+                }
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let keypair = Keypair::from_hex_key(KEY).unwrap();
+        let json = keypair
+            .to_keystore_with(&mut OsRng, "secret", Kdf::Pbkdf2 { c: 1024 })
+            .unwrap();
+        assert_eq!(keystore_address(&json).unwrap(), Some(keypair.address()));
+        let decrypted = Keypair::from_keystore(&json, "secret").unwrap();
+        assert_eq!(decrypted.signing_key(), keypair.signing_key());
+
+        // geth stores 20-byte hex address
+        let mut value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        value["address"] = keypair.address().as_hex()[2..].into();
+        assert!(Keypair::from_keystore(&value.to_string(), "secret").is_ok());
+        value["address"] = Keypair::generate(&mut OsRng).address().as_hex().into();
+        assert!(matches!(
+            Keypair::from_keystore(&value.to_string(), "secret"),
+            Err(SignerError::InvalidKeystore)
+        ));
+    }
+
+    #[test]
+    fn test_keystore_dir() {
+        let path = std::env::temp_dir().join(format!("heliosphere-keystore-{}", OsRng.next_u64()));
+        let mut dir = KeystoreDir::open(&path).unwrap();
+        let keypair = Keypair::generate(&mut OsRng);
+        let kdf = Kdf::Scrypt {
+            log_n: 10,
+            r: 8,
+            p: 1,
+        };
+        let address = dir.insert_with(&keypair, "secret", kdf).unwrap();
+        assert_eq!(address, keypair.address());
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(dir.path_of(&address).unwrap())
+                .unwrap()
+                .permissions()
+                .mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+        // leftover of interrupted write is ignored, replacing keeps the same file
+        let tmp = path.join(format!("{}.json.tmp", address));
+        std::fs::write(
+            &tmp,
+            keypair.to_keystore_with(&mut OsRng, "old", kdf).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            KeystoreDir::open(&path).unwrap().path_of(&address),
+            dir.path_of(&address)
+        );
+        std::fs::remove_file(tmp).unwrap();
+        dir.insert_with(&keypair, "secret", kdf).unwrap();
+        assert_eq!(std::fs::read_dir(&path).unwrap().count(), 1);
+        std::fs::write(path.join("README"), "not a keystore").unwrap();
+
+        let dir = KeystoreDir::open(&path).unwrap();
+        assert_eq!(dir.addresses().collect::<Vec<_>>(), [&address]);
+        let loaded = dir.load(&address, "secret").unwrap();
+        assert_eq!(loaded.signing_key(), keypair.signing_key());
+        assert!(matches!(
+            dir.load(&address, "wrong"),
+            Err(SignerError::InvalidPassword)
+        ));
+
+        let mut dir = dir;
+        dir.remove(&address).unwrap();
+        assert!(!dir.contains(&address));
+        assert!(KeystoreDir::open(&path)
+            .unwrap()
+            .addresses()
+            .next()
+            .is_none());
+        std::fs::remove_dir_all(path).unwrap();
+    }
+}
@@ -8,8 +8,24 @@ extern crate std;
 
 extern crate alloc;
 
+pub mod async_signer;
+#[cfg(feature = "std")]
+pub mod batch;
 pub mod error;
+#[cfg(feature = "hd")]
+pub mod hd;
 pub mod keypair;
+#[cfg(feature = "keystore")]
+pub mod keystore;
+#[cfg(feature = "libsecp256k1")]
+mod libsecp256k1;
+pub mod multisig;
+#[cfg(feature = "pkcs11")]
+pub mod pkcs11;
 pub mod signer;
+#[cfg(feature = "tip712")]
+pub mod tip712;
+#[cfg(feature = "vanity")]
+pub mod vanity;
 pub use k256;
-pub use signer::derive_address;
+pub use signer::{derive_address, recover_message_signer, verify_message, SignedTransaction};
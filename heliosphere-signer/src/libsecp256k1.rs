@@ -0,0 +1,85 @@
+//! libsecp256k1 backend (`libsecp256k1` feature): one-step recoverable signing and fast recovery
+
+use heliosphere_core::transaction::TronSignature;
+use k256::ecdsa::{RecoveryId, Signature, SigningKey, VerifyingKey};
+use secp256k1::{
+    ecdsa::{RecoverableSignature, RecoveryId as SecpRecoveryId},
+    Message, SecretKey,
+};
+use zeroize::Zeroizing;
+
+use crate::error::SignerError;
+
+/// Sign 32-byte prehash, returns low-S signature and its recovery id
+pub(crate) fn sign_prehash(key: &SigningKey, prehash: [u8; 32]) -> (Signature, RecoveryId) {
+    let bytes = Zeroizing::new(<[u8; 32]>::from(key.to_bytes()));
+    let mut secret = SecretKey::from_secret_bytes(*bytes).expect("Valid k256 key");
+    let signature =
+        RecoverableSignature::sign_ecdsa_recoverable(Message::from_digest(prehash), &secret);
+    secret.non_secure_erase();
+    let (rec_id, rs) = signature.serialize_compact();
+    (
+        Signature::from_slice(&rs).expect("Valid libsecp256k1 signature"),
+        RecoveryId::from_byte(rec_id.to_u8()).expect("Valid libsecp256k1 recovery id"),
+    )
+}
+
+/// Recover public key from 32-byte prehash
+pub(crate) fn recover_public_key(
+    prehash: [u8; 32],
+    signature: &TronSignature,
+) -> Result<VerifyingKey, SignerError> {
+    // same acceptance rules as k256: scalars in range, low S
+    let parsed = Signature::from_scalars(signature.r, signature.s)
+        .map_err(|_| SignerError::InvalidSignature)?;
+    if parsed.normalize_s().is_some() {
+        return Err(SignerError::InvalidSignature);
+    }
+    let rec_id = SecpRecoveryId::try_from(i32::from(signature.recovery_id))
+        .map_err(|_| SignerError::InvalidSignature)?;
+    let key = RecoverableSignature::from_compact(&parsed.to_bytes(), rec_id)
+        .and_then(|signature| signature.recover_ecdsa(Message::from_digest(prehash)))
+        .map_err(|_| SignerError::InvalidSignature)?;
+    VerifyingKey::from_sec1_bytes(&key.serialize_uncompressed())
+        .map_err(|_| SignerError::InvalidSignature)
+}
+
+#[cfg(test)]
+mod test {
+    use k256::ecdsa::hazmat::bits2field;
+    use sha3::{Digest, Keccak256};
+
+    use super::*;
+    use crate::signer::to_tron_signature;
+
+    #[test]
+    fn test_matches_k256() {
+        let key = SigningKey::from_slice(
+            &hex::decode("b224f69fe10604d71263971ecd9cdc9f2fc59d0e7ddb6c9df2aa8631423d8cb0")
+                .unwrap(),
+        )
+        .unwrap();
+        for i in 0u32..64 {
+            let prehash: [u8; 32] = Keccak256::digest(i.to_be_bytes()).into();
+            let expected = key.sign_prehash_recoverable(&prehash).unwrap();
+            let (signature, rec_id) = sign_prehash(&key, prehash);
+            assert_eq!((signature, rec_id), expected);
+
+            let tron_signature = to_tron_signature(&signature, rec_id);
+            assert_eq!(
+                recover_public_key(prehash, &tron_signature).unwrap(),
+                *key.verifying_key()
+            );
+            // high S counterpart is rejected like k256 does
+            let high_s = -*signature.s();
+            let high = TronSignature {
+                s: bits2field::<k256::Secp256k1>(&high_s.to_bytes())
+                    .unwrap()
+                    .into(),
+                recovery_id: rec_id.to_byte() ^ 1,
+                ..tron_signature
+            };
+            assert!(recover_public_key(prehash, &high).is_err());
+        }
+    }
+}
@@ -0,0 +1,195 @@
+//! Multi-signature helpers: collect signatures from several signers and check permission threshold
+
+use alloc::vec::Vec;
+use heliosphere_core::{contract::Permission, transaction::Transaction, Address};
+
+use crate::{
+    error::SignerError,
+    signer::{SignedTransaction, Signer},
+};
+
+/// Signature weight of a transaction against account permission
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignatureWeight {
+    /// Sum of weights of permission keys that signed
+    pub current_weight: i64,
+    /// Permission threshold
+    pub threshold: i64,
+    /// Permission keys that signed
+    pub approved: Vec<Address>,
+    /// Permission keys that did not sign yet
+    pub pending: Vec<Address>,
+}
+
+impl SignatureWeight {
+    /// Whether collected signatures reach permission threshold
+    pub fn is_enough(&self) -> bool {
+        self.current_weight >= self.threshold
+    }
+}
+
+/// Sign transaction with each of signers, appending their signatures
+pub fn sign_transaction_with<'a, S, I>(tx: &mut Transaction, signers: I) -> Result<(), S::Error>
+where
+    S: Signer + 'a,
+    I: IntoIterator<Item = &'a S>,
+{
+    for signer in signers {
+        signer.sign_transaction(tx)?;
+    }
+    Ok(())
+}
+
+/// Append signatures of `other` (same transaction signed elsewhere) which `tx` does not have yet
+pub fn merge_signatures(tx: &mut Transaction, other: &Transaction) -> Result<(), SignerError> {
+    if tx.tx_id != other.tx_id {
+        return Err(SignerError::TxIdMismatch);
+    }
+    for signature in &other.signature {
+        if !tx.signature.contains(signature) {
+            tx.signature.push(*signature);
+        }
+    }
+    Ok(())
+}
+
+/// Recover signers of transaction and check them against permission (as java-tron does):
+/// every signer must be a permission key and sign only once.
+pub fn signature_weight(
+    tx: &Transaction,
+    permission: &Permission,
+) -> Result<SignatureWeight, SignerError> {
+    if tx.permission_id() != permission.id {
+        return Err(SignerError::PermissionMismatch);
+    }
+    let mut current_weight = 0;
+    let mut approved = Vec::new();
+    for (_, address) in tx.recover_signers()? {
+        let key = permission
+            .keys
+            .iter()
+            .find(|key| key.address == address)
+            .ok_or(SignerError::SignerNotInPermission(address))?;
+        if approved.contains(&address) {
+            return Err(SignerError::DuplicateSigner(address));
+        }
+        approved.push(address);
+        current_weight += key.weight;
+    }
+    let pending = permission
+        .keys
+        .iter()
+        .map(|key| key.address)
+        .filter(|address| !approved.contains(address))
+        .collect();
+    Ok(SignatureWeight {
+        current_weight,
+        threshold: permission.threshold,
+        approved,
+        pending,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use alloc::vec;
+    use heliosphere_core::{
+        block::BlockId,
+        contract::{Key, PermissionType, TransferContract},
+        transaction::{TransactionBuilder, TronSignature},
+    };
+
+    use crate::keypair::Keypair;
+
+    use super::*;
+
+    fn keypair(n: u8) -> Keypair {
+        Keypair::from_hex_key(&hex::encode([n; 32])).unwrap()
+    }
+
+    fn setup() -> (Vec<Keypair>, Permission, Transaction) {
+        let keypairs: Vec<_> = (1..=3).map(keypair).collect();
+        let permission = Permission {
+            r#type: PermissionType::Active,
+            id: 2,
+            permission_name: "treasury".into(),
+            threshold: 2,
+            parent_id: 0,
+            operations: vec![0xff; 32],
+            keys: keypairs
+                .iter()
+                .map(|k| Key {
+                    address: k.address(),
+                    weight: 1,
+                })
+                .collect(),
+        };
+        let tx = TransactionBuilder::new(TransferContract {
+            owner_address: keypair(9).address(),
+            to_address: keypairs[0].address(),
+            amount: 1,
+        })
+        .with_ref_block_id(BlockId([0x01; 32]))
+        .with_timestamp(1)
+        .with_permission_id(2)
+        .build()
+        .unwrap();
+        (keypairs, permission, tx)
+    }
+
+    #[test]
+    fn test_two_of_three() {
+        let (keypairs, permission, tx) = setup();
+
+        let mut first = tx.clone();
+        keypairs[0].sign_transaction(&mut first).unwrap();
+        let weight = signature_weight(&first, &permission).unwrap();
+        assert_eq!(weight.current_weight, 1);
+        assert!(!weight.is_enough());
+        assert_eq!(
+            weight.pending,
+            vec![keypairs[1].address(), keypairs[2].address()]
+        );
+
+        let mut second = tx.clone();
+        keypairs[2].sign_transaction(&mut second).unwrap();
+        merge_signatures(&mut first, &second).unwrap();
+        merge_signatures(&mut first, &second).unwrap();
+        assert_eq!(first.signature.len(), 2);
+        let weight = signature_weight(&first, &permission).unwrap();
+        assert!(weight.is_enough());
+        assert_eq!(
+            weight.approved,
+            vec![keypairs[0].address(), keypairs[2].address()]
+        );
+    }
+
+    #[test]
+    fn test_invalid_signers() {
+        let (keypairs, permission, mut tx) = setup();
+        sign_transaction_with(&mut tx, [&keypairs[1], &keypairs[1]]).unwrap();
+        assert!(matches!(
+            signature_weight(&tx, &permission),
+            Err(SignerError::DuplicateSigner(_))
+        ));
+
+        tx.signature.clear();
+        keypair(9).sign_transaction(&mut tx).unwrap();
+        assert!(matches!(
+            signature_weight(&tx, &permission),
+            Err(SignerError::SignerNotInPermission(_))
+        ));
+
+        tx.signature = vec![TronSignature::new([0; 32], [0; 32], 0).unwrap()];
+        assert!(matches!(
+            signature_weight(&tx, &permission),
+            Err(SignerError::InvalidSignature)
+        ));
+
+        tx.set_permission_id(0).unwrap();
+        assert!(matches!(
+            signature_weight(&tx, &permission),
+            Err(SignerError::PermissionMismatch)
+        ));
+    }
+}
@@ -0,0 +1,288 @@
+//! PKCS#11 (HSM) signer
+//!
+//! Private key never leaves the token: the token produces raw ECDSA `r || s` signatures,
+//! low-S normalization and recovery id computation are done locally against the public key.
+use alloc::{borrow::ToOwned, format, string::ToString};
+use std::{path::Path, sync::Mutex};
+
+use cryptoki::{
+    context::{CInitializeArgs, CInitializeFlags, Pkcs11},
+    error::RvError,
+    mechanism::Mechanism,
+    object::{Attribute, AttributeType, KeyType, ObjectClass, ObjectHandle},
+    session::{Session, UserType},
+    types::AuthPin,
+};
+use k256::ecdsa::{RecoveryId, Signature, VerifyingKey};
+
+use crate::{error::SignerError, signer::Signer};
+
+/// DER encoded secp256k1 curve OID (1.3.132.0.10), the `CKA_EC_PARAMS` of TRON keys
+pub const SECP256K1_EC_PARAMS: [u8; 7] = [0x06, 0x05, 0x2b, 0x81, 0x04, 0x00, 0x0a];
+
+/// Signer backed by secp256k1 key in PKCS#11 token
+#[derive(Debug)]
+pub struct Pkcs11Signer {
+    // PKCS#11 sessions must not be used concurrently
+    session: Mutex<Session>,
+    key: ObjectHandle,
+    verifying_key: VerifyingKey,
+}
+
+impl Pkcs11Signer {
+    /// Load PKCS#11 module, log into token with `token_label` as user and find private key with `key_label`
+    pub fn open(
+        module: impl AsRef<Path>,
+        token_label: &str,
+        pin: &str,
+        key_label: &str,
+    ) -> Result<Self, SignerError> {
+        let pkcs11 = Pkcs11::new(module)?;
+        match pkcs11.initialize(CInitializeArgs::new(CInitializeFlags::OS_LOCKING_OK)) {
+            Err(cryptoki::error::Error::Pkcs11(RvError::CryptokiAlreadyInitialized, _))
+            | Ok(()) => {}
+            Err(e) => return Err(e.into()),
+        }
+        let mut slot = None;
+        for s in pkcs11.get_slots_with_token()? {
+            if pkcs11.get_token_info(s)?.label() == token_label {
+                slot = Some(s);
+                break;
+            }
+        }
+        let slot =
+            slot.ok_or_else(|| SignerError::Pkcs11(format!("token {} not found", token_label)))?;
+        let session = pkcs11.open_ro_session(slot)?;
+        session.login(UserType::User, Some(&AuthPin::new(pin.into())))?;
+        Self::from_session(session, key_label)
+    }
+
+    /// Use logged in session, find private key with `key_label`
+    ///
+    /// Public key is read from public key object with the same `CKA_ID` (or label if private key has no id).
+    pub fn from_session(session: Session, key_label: &str) -> Result<Self, SignerError> {
+        let label = Attribute::Label(key_label.as_bytes().to_vec());
+        let key = match session.find_objects(&[
+            Attribute::Class(ObjectClass::PRIVATE_KEY),
+            Attribute::KeyType(KeyType::EC),
+            label.clone(),
+        ])?[..]
+        {
+            [key] => key,
+            [] => return Err(SignerError::KeyNotFound),
+            _ => {
+                return Err(SignerError::Pkcs11(format!(
+                    "multiple keys labeled {}",
+                    key_label
+                )))
+            }
+        };
+        let id = match session.get_attributes(key, &[AttributeType::Id])?.pop() {
+            Some(Attribute::Id(id)) if !id.is_empty() => Attribute::Id(id),
+            _ => label,
+        };
+        let public_key = *session
+            .find_objects(&[
+                Attribute::Class(ObjectClass::PUBLIC_KEY),
+                Attribute::KeyType(KeyType::EC),
+                id,
+            ])?
+            .first()
+            .ok_or(SignerError::KeyNotFound)?;
+
+        let (mut params, mut point) = (None, None);
+        for attr in session.get_attributes(
+            public_key,
+            &[AttributeType::EcParams, AttributeType::EcPoint],
+        )? {
+            match attr {
+                Attribute::EcParams(p) => params = Some(p),
+                Attribute::EcPoint(p) => point = Some(p),
+                _ => {}
+            }
+        }
+        if params.as_deref() != Some(&SECP256K1_EC_PARAMS[..]) {
+            return Err(SignerError::Pkcs11("key is not secp256k1".to_owned()));
+        }
+        let verifying_key = parse_ec_point(&point.unwrap_or_default())?;
+        Ok(Self {
+            session: Mutex::new(session),
+            key,
+            verifying_key,
+        })
+    }
+}
+
+/// Parse `CKA_EC_POINT`: DER OCTET STRING with SEC1 point, some modules return bare point
+fn parse_ec_point(point: &[u8]) -> Result<VerifyingKey, SignerError> {
+    match point {
+        [0x04, len, inner @ ..] if *len as usize == inner.len() => {
+            VerifyingKey::from_sec1_bytes(inner).or_else(|_| VerifyingKey::from_sec1_bytes(point))
+        }
+        _ => VerifyingKey::from_sec1_bytes(point),
+    }
+    .map_err(|_| SignerError::InvalidKey)
+}
+
+impl Signer for Pkcs11Signer {
+    type Error = SignerError;
+
+    fn public_key(&self) -> VerifyingKey {
+        self.verifying_key
+    }
+
+    fn sign_prehash(&self, prehash: &[u8]) -> Result<(Signature, RecoveryId), Self::Error> {
+        let raw = self.session.lock().expect("Poisoned lock").sign(
+            &Mechanism::Ecdsa,
+            self.key,
+            prehash,
+        )?;
+        let s = Signature::from_slice(&raw).map_err(|_| SignerError::InvalidSignature)?;
+        // tokens are free to return high S, which TRON rejects
+        let s = s.normalize_s().unwrap_or(s);
+        let rec_id = RecoveryId::trial_recovery_from_prehash(&self.verifying_key, prehash, &s)
+            .map_err(|_| SignerError::InvalidSignature)?;
+        Ok((s, rec_id))
+    }
+}
+
+impl From<cryptoki::error::Error> for SignerError {
+    fn from(e: cryptoki::error::Error) -> Self {
+        SignerError::Pkcs11(e.to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use heliosphere_core::{
+        block::BlockId, contract::TransferContract, transaction::TransactionBuilder,
+    };
+
+    use alloc::{string::String, vec};
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::signer::{recover_message_signer, SignedTransaction};
+
+    const SO_PIN: &str = "abcdef";
+    const USER_PIN: &str = "fedcba";
+
+    /// SoftHSM token directory, deleted on drop
+    struct SoftHsmToken {
+        module: String,
+        dir: PathBuf,
+    }
+
+    impl Drop for SoftHsmToken {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.dir);
+        }
+    }
+
+    /// Create fresh SoftHSM token with generated secp256k1 key
+    fn softhsm_token() -> SoftHsmToken {
+        let module = std::env::var("SOFTHSM2_MODULE").expect(
+            "SOFTHSM2_MODULE must point to SoftHSM library (e.g. /usr/lib/softhsm/libsofthsm2.so)",
+        );
+        let dir = std::env::temp_dir().join(format!("heliosphere-softhsm-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("tokens")).unwrap();
+        let token = SoftHsmToken {
+            module: module.clone(),
+            dir: dir.clone(),
+        };
+        let conf = dir.join("softhsm2.conf");
+        std::fs::write(
+            &conf,
+            format!("directories.tokendir = {}\n", dir.join("tokens").display()),
+        )
+        .unwrap();
+        std::env::set_var("SOFTHSM2_CONF", &conf);
+
+        let pkcs11 = Pkcs11::new(&module).unwrap();
+        pkcs11
+            .initialize(CInitializeArgs::new(CInitializeFlags::OS_LOCKING_OK))
+            .unwrap();
+        let slot = pkcs11.get_slots_with_token().unwrap()[0];
+        let so_pin = AuthPin::new(SO_PIN.into());
+        pkcs11.init_token(slot, &so_pin, "heliosphere").unwrap();
+        let session = pkcs11.open_rw_session(slot).unwrap();
+        session.login(UserType::So, Some(&so_pin)).unwrap();
+        session.init_pin(&AuthPin::new(USER_PIN.into())).unwrap();
+        session.logout().unwrap();
+        session
+            .login(UserType::User, Some(&AuthPin::new(USER_PIN.into())))
+            .unwrap();
+        session
+            .generate_key_pair(
+                &Mechanism::EccKeyPairGen,
+                &[
+                    Attribute::Token(true),
+                    Attribute::Verify(true),
+                    Attribute::EcParams(SECP256K1_EC_PARAMS.to_vec()),
+                    Attribute::Id(vec![1]),
+                    Attribute::Label(b"tron".to_vec()),
+                ],
+                &[
+                    Attribute::Token(true),
+                    Attribute::Private(true),
+                    Attribute::Sensitive(true),
+                    Attribute::Sign(true),
+                    Attribute::Id(vec![1]),
+                    Attribute::Label(b"tron".to_vec()),
+                ],
+            )
+            .unwrap();
+        token
+    }
+
+    #[test]
+    fn test_parse_ec_point() {
+        let keypair = crate::keypair::Keypair::from_hex_key(
+            "b224f69fe10604d71263971ecd9cdc9f2fc59d0e7ddb6c9df2aa8631423d8cb0",
+        )
+        .unwrap();
+        let point = keypair
+            .public_key()
+            .to_encoded_point(false)
+            .as_bytes()
+            .to_vec();
+        let mut der = vec![0x04, point.len() as u8];
+        der.extend_from_slice(&point);
+        assert_eq!(parse_ec_point(&der).unwrap(), *keypair.public_key());
+        assert_eq!(parse_ec_point(&point).unwrap(), *keypair.public_key());
+        assert!(parse_ec_point(&der[..40]).is_err());
+    }
+
+    #[test]
+    #[ignore = "requires SoftHSM, set SOFTHSM2_MODULE and run with --ignored"]
+    fn test_softhsm() {
+        let token = softhsm_token();
+        let module = token.module.as_str();
+        assert!(matches!(
+            Pkcs11Signer::open(module, "heliosphere", USER_PIN, "missing"),
+            Err(SignerError::KeyNotFound)
+        ));
+        let signer = Pkcs11Signer::open(module, "heliosphere", USER_PIN, "tron").unwrap();
+        let address = signer.address();
+
+        // HSM may return high S, repeat to exercise normalization
+        for i in 0..16 {
+            let mut tx = TransactionBuilder::new(TransferContract {
+                owner_address: address,
+                to_address: address,
+                amount: i + 1,
+            })
+            .with_ref_block_id(BlockId([0x01; 32]))
+            .with_timestamp(1)
+            .build()
+            .unwrap();
+            signer.sign_transaction(&mut tx).unwrap();
+            assert!(tx.verify_signed_by(&address).unwrap());
+        }
+        let signature = signer.sign_message(b"hello").unwrap();
+        assert_eq!(
+            recover_message_signer(b"hello", &signature).unwrap(),
+            address
+        );
+    }
+}
@@ -1,13 +1,15 @@
 //! Signer utils
 
+use alloc::string::ToString;
 use alloc::vec::Vec;
-use alloc::{format, string::ToString};
 use core::fmt::Debug;
-use heliosphere_core::transaction::Transaction;
+use heliosphere_core::transaction::{Transaction, TransactionId, TronSignature};
 use heliosphere_core::Address;
 use k256::ecdsa::{RecoveryId, Signature, VerifyingKey};
 use sha3::{Digest, Keccak256};
 
+use crate::error::SignerError;
+
 const TRON_MESSAGE_PREFIX: &[u8] = b"\x19TRON Signed Message:\n";
 
 /// Derive Tron address from VerifyingKey
@@ -17,17 +19,50 @@ pub fn derive_address(key: &VerifyingKey) -> Address {
     let y = verifying_key.y().unwrap();
     let mut p_bytes = Vec::new();
     p_bytes.extend_from_slice(&[0x04]);
-    p_bytes.extend_from_slice(x.as_slice());
-    p_bytes.extend_from_slice(y.as_slice());
+    p_bytes.extend_from_slice(&x[..]);
+    p_bytes.extend_from_slice(&y[..]);
     if p_bytes.len() == 65 {
         p_bytes.remove(0);
     }
     let hash = Keccak256::digest(&p_bytes);
     let mut addr = [0x41; 21];
-    addr[1..].copy_from_slice(&hash.as_slice()[hash.len() - 20..]);
+    addr[1..].copy_from_slice(&hash[hash.len() - 20..]);
     Address::new(addr).unwrap()
 }
 
+/// Recover signer public key from prehash and signature
+pub fn recover_public_key(
+    prehash: &[u8],
+    signature: &TronSignature,
+) -> Result<VerifyingKey, SignerError> {
+    #[cfg(feature = "libsecp256k1")]
+    if let Ok(prehash) = <[u8; 32]>::try_from(prehash) {
+        return crate::libsecp256k1::recover_public_key(prehash, signature);
+    }
+    let rec_id =
+        RecoveryId::from_byte(signature.recovery_id).ok_or(SignerError::InvalidSignature)?;
+    let signature = Signature::from_scalars(signature.r, signature.s)
+        .map_err(|_| SignerError::InvalidSignature)?;
+    VerifyingKey::recover_from_prehash(prehash, &signature, rec_id)
+        .map_err(|_| SignerError::InvalidSignature)
+}
+
+/// Recover signer address from prehash and 65-byte signature `r || s || v` (v is 0/1 or 27/28)
+pub fn recover_address(prehash: &[u8], signature: &[u8]) -> Result<Address, SignerError> {
+    let signature =
+        TronSignature::from_bytes(signature).map_err(|_| SignerError::InvalidSignature)?;
+    Ok(derive_address(&recover_public_key(prehash, &signature)?))
+}
+
+pub(crate) fn to_tron_signature(signature: &Signature, rec_id: RecoveryId) -> TronSignature {
+    let (r, s) = signature.split_bytes();
+    TronSignature {
+        r: r.into(),
+        s: s.into(),
+        recovery_id: rec_id.to_byte(),
+    }
+}
+
 /// Compute keccak256([b"\x19TRON Signed Message:\n", message.len().to_string().as_bytes(), message])
 pub fn hash_message(message: &[u8]) -> Keccak256 {
     let mut buf = Vec::new();
@@ -39,6 +74,30 @@ pub fn hash_message(message: &[u8]) -> Keccak256 {
     digest
 }
 
+fn to_rsv(signature: &Signature, rec_id: RecoveryId) -> [u8; 65] {
+    let mut bytes = to_tron_signature(signature, rec_id).to_bytes();
+    bytes[64] += 27;
+    bytes
+}
+
+/// Recover address of TIP-191 (`signMessageV2`) message signer
+pub fn recover_message_signer(message: &[u8], signature: &[u8]) -> Result<Address, SignerError> {
+    recover_address(&hash_message(message).finalize(), signature)
+}
+
+/// Verify that TIP-191 message signature was produced by `address`, returns recovered signer
+pub fn verify_message(
+    address: &Address,
+    message: &[u8],
+    signature: &[u8],
+) -> Result<Address, SignerError> {
+    let signer = recover_message_signer(message, signature)?;
+    if signer != *address {
+        return Err(SignerError::UnexpectedSigner(signer));
+    }
+    Ok(signer)
+}
+
 /// Generic signer
 pub trait Signer {
     /// Signer error
@@ -58,11 +117,50 @@ pub trait Signer {
     /// Sign transaction
     fn sign_transaction(&self, tx: &mut Transaction) -> Result<(), Self::Error> {
         let (signature, rec_id) = self.sign_prehash(&tx.tx_id.0)?;
-        tx.signature.push(format!(
-            "{}{:02}",
-            hex::encode(signature.to_bytes()),
-            rec_id.to_byte()
-        ));
+        tx.signature.push(to_tron_signature(&signature, rec_id));
         Ok(())
     }
+
+    /// Sign message (TIP-191), returns 65-byte signature `r || s || v` (v is 27/28) compatible with TronWeb `signMessageV2`
+    fn sign_message(&self, message: &[u8]) -> Result<[u8; 65], Self::Error> {
+        let (signature, rec_id) = self.sign_prehash(&hash_message(message).finalize())?;
+        Ok(to_rsv(&signature, rec_id))
+    }
+
+    /// Sign TIP-712 typed data, returns 65-byte signature `r || s || v` (v is 27/28)
+    #[cfg(feature = "tip712")]
+    fn sign_typed_data(&self, data: &dyn crate::tip712::Tip712) -> Result<[u8; 65], Self::Error> {
+        let (signature, rec_id) = self.sign_prehash(&data.tip712_signing_hash())?;
+        Ok(to_rsv(&signature, rec_id))
+    }
+}
+
+/// Signer recovery for signed transactions
+pub trait SignedTransaction {
+    /// Recover public keys and addresses of all transaction signers (in signature order).
+    /// Fails if tx id does not match raw data or any signature is invalid.
+    fn recover_signers(&self) -> Result<Vec<(VerifyingKey, Address)>, SignerError>;
+
+    /// Check whether transaction carries a valid signature of `address`
+    fn verify_signed_by(&self, address: &Address) -> Result<bool, SignerError> {
+        Ok(self
+            .recover_signers()?
+            .iter()
+            .any(|(_, signer)| signer == address))
+    }
+}
+
+impl SignedTransaction for Transaction {
+    fn recover_signers(&self) -> Result<Vec<(VerifyingKey, Address)>, SignerError> {
+        if TransactionId::from_raw_data_bytes(&self.raw_data_bytes) != self.tx_id {
+            return Err(SignerError::TxIdMismatch);
+        }
+        self.signature
+            .iter()
+            .map(|signature| {
+                let key = recover_public_key(&self.tx_id.0, signature)?;
+                Ok((key, derive_address(&key)))
+            })
+            .collect()
+    }
 }
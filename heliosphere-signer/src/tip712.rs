@@ -0,0 +1,430 @@
+//! TIP-712 typed structured data hashing (TRON flavour of EIP-712)
+//!
+//! Hashing rules are identical to EIP-712 except that `address` values are TRON addresses
+//! (base58 or 41-prefixed hex), which are hashed as 20-byte EVM addresses, and `trcToken` values are encoded
+//! as `uint256` (the type name is kept in `encodeType`).
+
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    string::String,
+    vec::Vec,
+};
+use core::str::FromStr;
+
+use alloy_dyn_abi::DynSolValue;
+pub use alloy_dyn_abi::TypedData;
+use alloy_primitives::{keccak256, B256};
+pub use alloy_sol_types::{Eip712Domain, SolStruct};
+use heliosphere_core::Address;
+use serde_json::Value;
+
+use crate::{error::SignerError, signer::recover_address};
+
+/// Data that can be signed according to TIP-712
+pub trait Tip712 {
+    /// Compute `keccak256("\x19\x01" || domainSeparator || hashStruct(message))`
+    fn tip712_signing_hash(&self) -> [u8; 32];
+}
+
+/// Statically typed struct (e.g. defined with alloy `sol!`) with signing domain
+impl<T: SolStruct> Tip712 for (&T, &Eip712Domain) {
+    fn tip712_signing_hash(&self) -> [u8; 32] {
+        self.0.eip712_signing_hash(self.1).0
+    }
+}
+
+/// Build signing domain with TRON verifying contract
+pub fn tip712_domain(
+    name: impl Into<String>,
+    version: impl Into<String>,
+    chain_id: u64,
+    verifying_contract: Address,
+) -> Eip712Domain {
+    Eip712Domain::new(
+        Some(name.into().into()),
+        Some(version.into().into()),
+        Some(alloy_primitives::U256::from(chain_id)),
+        Some(verifying_contract.into()),
+        None,
+    )
+}
+
+/// TIP-712 JSON typed data document (`types`, `primaryType`, `domain`, `message`)
+#[derive(Debug, Clone)]
+pub struct Tip712TypedData {
+    data: TypedData,
+    types: BTreeMap<String, Vec<(String, String)>>,
+    hash: [u8; 32],
+}
+
+impl Tip712TypedData {
+    /// Parse typed data document, converting TRON addresses and `trcToken` types
+    pub fn from_json(json: &Value) -> Result<Self, SignerError> {
+        let mut json = json.clone();
+        let types = normalize_document(&mut json)?;
+        let data: TypedData =
+            serde_json::from_value(json).map_err(|_| SignerError::InvalidTypedData)?;
+        let mut this = Self {
+            data,
+            types,
+            hash: [0; 32],
+        };
+        let mut buf = [0; 66];
+        buf[..2].copy_from_slice(b"\x19\x01");
+        buf[2..34].copy_from_slice(this.data.domain.separator().as_slice());
+        // compatibility with eth-sig-util: domain only when primary type is the domain
+        let len = if this.data.primary_type != "EIP712Domain" {
+            let message = this
+                .data
+                .coerce()
+                .map_err(|_| SignerError::InvalidTypedData)?;
+            buf[34..].copy_from_slice(this.data_word(&message)?.as_slice());
+            66
+        } else {
+            34
+        };
+        this.hash = keccak256(&buf[..len]).0;
+        Ok(this)
+    }
+
+    /// Get parsed (EVM-normalized) typed data, `trcToken` types are replaced by `uint256`
+    pub fn typed_data(&self) -> &TypedData {
+        &self.data
+    }
+
+    /// Get `encodeType` of primary type with original (TRON) type names
+    pub fn encode_type(&self) -> Result<String, SignerError> {
+        self.encode_struct_type(&self.data.primary_type)
+    }
+
+    fn encode_struct_type(&self, name: &str) -> Result<String, SignerError> {
+        let mut deps = BTreeSet::new();
+        self.collect_dependencies(name, &mut deps)?;
+        deps.remove(name);
+        let mut encoded = String::new();
+        for name in core::iter::once(name).chain(deps) {
+            encoded.push_str(name);
+            encoded.push('(');
+            for (i, (field, ty)) in self.types[name].iter().enumerate() {
+                if i > 0 {
+                    encoded.push(',');
+                }
+                encoded.push_str(ty);
+                encoded.push(' ');
+                encoded.push_str(field);
+            }
+            encoded.push(')');
+        }
+        Ok(encoded)
+    }
+
+    fn collect_dependencies<'a>(
+        &'a self,
+        name: &'a str,
+        deps: &mut BTreeSet<&'a str>,
+    ) -> Result<(), SignerError> {
+        let fields = self.types.get(name).ok_or(SignerError::InvalidTypedData)?;
+        if !deps.insert(name) {
+            return Ok(());
+        }
+        for (_, ty) in fields {
+            let root = ty.split('[').next().unwrap_or_default();
+            if self.types.contains_key(root) {
+                self.collect_dependencies(root, deps)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// EIP-712 `encodeData` word of a value: atomic values as is, others hashed
+    fn data_word(&self, value: &DynSolValue) -> Result<B256, SignerError> {
+        if let Some(word) = value.as_word() {
+            return Ok(word);
+        }
+        let mut bytes = Vec::new();
+        let data = match value {
+            DynSolValue::CustomStruct { name, tuple, .. } => {
+                bytes.extend_from_slice(keccak256(self.encode_struct_type(name)?).as_slice());
+                for v in tuple {
+                    bytes.extend_from_slice(self.data_word(v)?.as_slice());
+                }
+                &bytes[..]
+            }
+            DynSolValue::Array(inner) | DynSolValue::FixedArray(inner) => {
+                for v in inner {
+                    bytes.extend_from_slice(self.data_word(v)?.as_slice());
+                }
+                &bytes[..]
+            }
+            DynSolValue::Bytes(buf) => buf,
+            DynSolValue::String(s) => s.as_bytes(),
+            _ => return Err(SignerError::InvalidTypedData),
+        };
+        Ok(keccak256(data))
+    }
+}
+
+impl FromStr for Tip712TypedData {
+    type Err = SignerError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_json(&serde_json::from_str(s).map_err(|_| SignerError::InvalidTypedData)?)
+    }
+}
+
+impl Tip712 for Tip712TypedData {
+    fn tip712_signing_hash(&self) -> [u8; 32] {
+        self.hash
+    }
+}
+
+/// Recover address of TIP-712 typed data signer
+pub fn recover_typed_data_signer(
+    data: &impl Tip712,
+    signature: &[u8],
+) -> Result<Address, SignerError> {
+    recover_address(&data.tip712_signing_hash(), signature)
+}
+
+/// Verify that TIP-712 typed data signature was produced by `address`, returns recovered signer
+pub fn verify_typed_data(
+    address: &Address,
+    data: &impl Tip712,
+    signature: &[u8],
+) -> Result<Address, SignerError> {
+    let signer = recover_typed_data_signer(data, signature)?;
+    if signer != *address {
+        return Err(SignerError::UnexpectedSigner(signer));
+    }
+    Ok(signer)
+}
+
+/// Convert TRON addresses to EVM ones and `trcToken` types to `uint256`, returns original struct types
+fn normalize_document(
+    json: &mut Value,
+) -> Result<BTreeMap<String, Vec<(String, String)>>, SignerError> {
+    let doc = json.as_object_mut().ok_or(SignerError::InvalidTypedData)?;
+    let mut types = doc
+        .get("types")
+        .and_then(Value::as_object)
+        .cloned()
+        .ok_or(SignerError::InvalidTypedData)?;
+    let original = types
+        .iter()
+        .map(|(name, fields)| {
+            let fields = fields
+                .as_array()
+                .ok_or(SignerError::InvalidTypedData)?
+                .iter()
+                .map(|f| {
+                    Some((
+                        f.get("name")?.as_str()?.into(),
+                        f.get("type")?.as_str()?.into(),
+                    ))
+                })
+                .collect::<Option<_>>()
+                .ok_or(SignerError::InvalidTypedData)?;
+            Ok((name.clone(), fields))
+        })
+        .collect::<Result<_, SignerError>>()?;
+    for fields in types.values_mut() {
+        for field in fields.as_array_mut().into_iter().flatten() {
+            if let Some(ty) = field.get_mut("type") {
+                if let Some(s) = ty.as_str().and_then(|s| s.strip_prefix("trcToken")) {
+                    *ty = Value::String(alloc::format!("uint256{}", s));
+                }
+            }
+        }
+    }
+    if let Some(contract) = doc
+        .get_mut("domain")
+        .and_then(|domain| domain.get_mut("verifyingContract"))
+    {
+        normalize_value(&types, "address", contract)?;
+    }
+    if let (Some(primary), Some(message)) = (
+        doc.get("primaryType")
+            .and_then(Value::as_str)
+            .map(String::from),
+        doc.get_mut("message"),
+    ) {
+        normalize_value(&types, &primary, message)?;
+    }
+    doc.insert("types".into(), Value::Object(types));
+    Ok(original)
+}
+
+fn normalize_value(
+    types: &serde_json::Map<String, Value>,
+    ty: &str,
+    value: &mut Value,
+) -> Result<(), SignerError> {
+    if let Some(inner) = ty.strip_suffix(']') {
+        let inner = &inner[..inner.rfind('[').ok_or(SignerError::InvalidTypedData)?];
+        for item in value.as_array_mut().into_iter().flatten() {
+            normalize_value(types, inner, item)?;
+        }
+    } else if ty == "address" {
+        let address: Address = value
+            .as_str()
+            .and_then(|s| s.parse().ok())
+            .ok_or(SignerError::InvalidTypedData)?;
+        *value = Value::String(alloc::format!("0x{}", &address.as_hex()[2..]));
+    } else if let Some(fields) = types.get(ty).and_then(Value::as_array) {
+        let fields: Vec<(&str, &str)> = fields
+            .iter()
+            .filter_map(|f| Some((f.get("name")?.as_str()?, f.get("type")?.as_str()?)))
+            .collect();
+        for (name, ty) in fields {
+            if let Some(v) = value.get_mut(name) {
+                normalize_value(types, ty, v)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{keypair::Keypair, signer::Signer};
+    use alloy_sol_types::sol;
+
+    sol! {
+        struct Person {
+            string name;
+            address wallet;
+        }
+
+        struct Mail {
+            Person from;
+            Person to;
+            string contents;
+        }
+    }
+
+    const MAIL_HASH: &str = "be609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2";
+
+    fn tron(evm: &str) -> Address {
+        alloc::format!("41{}", evm).parse().unwrap()
+    }
+
+    #[test]
+    fn test_sol_struct_hash() {
+        let mail = Mail {
+            from: Person {
+                name: "Cow".into(),
+                wallet: tron("cd2a3d9f938e13cd947ec05abc7fe734df8dd826").into(),
+            },
+            to: Person {
+                name: "Bob".into(),
+                wallet: tron("bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb").into(),
+            },
+            contents: "Hello, Bob!".into(),
+        };
+        let domain = tip712_domain(
+            "Ether Mail",
+            "1",
+            1,
+            tron("cccccccccccccccccccccccccccccccccccccccc"),
+        );
+        assert_eq!(
+            hex::encode((&mail, &domain).tip712_signing_hash()),
+            MAIL_HASH
+        );
+    }
+
+    #[test]
+    fn test_json_hash_and_sign() {
+        let doc = serde_json::json!({
+            "types": {
+                "EIP712Domain": [
+                    { "name": "name", "type": "string" },
+                    { "name": "version", "type": "string" },
+                    { "name": "chainId", "type": "uint256" },
+                    { "name": "verifyingContract", "type": "address" }
+                ],
+                "Person": [
+                    { "name": "name", "type": "string" },
+                    { "name": "wallet", "type": "address" }
+                ],
+                "Mail": [
+                    { "name": "from", "type": "Person" },
+                    { "name": "to", "type": "Person" },
+                    { "name": "contents", "type": "string" }
+                ]
+            },
+            "primaryType": "Mail",
+            "domain": {
+                "name": "Ether Mail",
+                "version": "1",
+                "chainId": 1,
+                "verifyingContract": tron("cccccccccccccccccccccccccccccccccccccccc").as_base58()
+            },
+            "message": {
+                "from": {
+                    "name": "Cow",
+                    "wallet": tron("cd2a3d9f938e13cd947ec05abc7fe734df8dd826").as_base58()
+                },
+                "to": {
+                    "name": "Bob",
+                    "wallet": tron("bbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbbb").as_hex()
+                },
+                "contents": "Hello, Bob!"
+            }
+        });
+        let data = Tip712TypedData::from_json(&doc).unwrap();
+        assert_eq!(hex::encode(data.tip712_signing_hash()), MAIL_HASH);
+
+        let keypair = Keypair::from_hex_key(
+            "b224f69fe10604d71263971ecd9cdc9f2fc59d0e7ddb6c9df2aa8631423d8cb0",
+        )
+        .unwrap();
+        let signature = keypair.sign_typed_data(&data).unwrap();
+        assert_eq!(
+            verify_typed_data(&keypair.address(), &data, &signature).unwrap(),
+            keypair.address()
+        );
+    }
+
+    #[test]
+    fn test_trc_token_type() {
+        let doc = serde_json::json!({
+            "types": {
+                "EIP712Domain": [{ "name": "name", "type": "string" }],
+                "Deposit": [
+                    { "name": "token", "type": "trcToken" },
+                    { "name": "owners", "type": "address[]" }
+                ]
+            },
+            "primaryType": "Deposit",
+            "domain": { "name": "Vault" },
+            "message": {
+                "token": 1002000,
+                "owners": ["TJ4bdYW5G7EXrzWJY1e1nduY3ihEzU1G4R"]
+            }
+        });
+        let data = Tip712TypedData::from_json(&doc).unwrap();
+        assert_eq!(
+            data.encode_type().unwrap(),
+            "Deposit(trcToken token,address[] owners)"
+        );
+        let mut uint_doc = doc.clone();
+        uint_doc["types"]["Deposit"][0]["type"] = "uint256".into();
+        let uint_data = Tip712TypedData::from_json(&uint_doc).unwrap();
+        assert_eq!(
+            uint_data
+                .data_word(&uint_data.data.coerce().unwrap())
+                .unwrap(),
+            keccak256(
+                [
+                    keccak256("Deposit(uint256 token,address[] owners)").as_slice(),
+                    &uint_data.data.encode_data().unwrap(),
+                ]
+                .concat()
+            )
+        );
+        assert_ne!(data.tip712_signing_hash(), uint_data.tip712_signing_hash());
+        assert!(Tip712TypedData::from_json(&serde_json::json!({ "types": {} })).is_err());
+    }
+}
@@ -0,0 +1,313 @@
+//! Vanity address search
+//!
+//! Keypairs are generated with [`Keypair::generate`] on all CPU cores until
+//! [`derive_address`](crate::signer::derive_address) of the public key matches a [`Pattern`].
+use alloc::{borrow::ToOwned, string::String};
+use core::time::Duration;
+use std::{
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        mpsc::{self, RecvTimeoutError},
+    },
+    thread,
+    time::Instant,
+};
+
+use heliosphere_core::Address;
+use rand_core::OsRng;
+
+use crate::{error::SignerError, keypair::Keypair, signer::Signer};
+
+const ALPHABET: &str = "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// Keys generated between checks of stop flags
+const BATCH: u64 = 64;
+
+/// Base58 address pattern
+#[derive(Debug, Clone)]
+pub enum Pattern {
+    /// Address starts with string (including leading `T`)
+    Prefix(String),
+    /// Address ends with string
+    Suffix(String),
+    /// Address matches regular expression
+    Regex(regex::Regex),
+}
+
+/// Range of characters which can follow `T` in TRON addresses
+fn second_chars() -> (usize, usize) {
+    let second = |byte| {
+        let mut bytes = [byte; 21];
+        bytes[0] = 0x41;
+        let address = Address::new(bytes).unwrap().as_base58();
+        ALPHABET.find(&address[1..2]).unwrap()
+    };
+    (second(0x00), second(0xff))
+}
+
+impl Pattern {
+    /// Prefix pattern, fails if no address can match it
+    pub fn prefix(prefix: &str) -> Result<Self, SignerError> {
+        let mut chars = prefix.chars();
+        if prefix.len() > 34 || chars.next() != Some('T') || !chars.all(|c| ALPHABET.contains(c)) {
+            return Err(SignerError::InvalidVanityPattern);
+        }
+        if let Some(c) = prefix.get(1..2) {
+            let (min, max) = second_chars();
+            if !(min..=max).contains(&ALPHABET.find(c).unwrap()) {
+                return Err(SignerError::InvalidVanityPattern);
+            }
+        }
+        Ok(Pattern::Prefix(prefix.to_owned()))
+    }
+
+    /// Suffix pattern, fails if no address can match it
+    pub fn suffix(suffix: &str) -> Result<Self, SignerError> {
+        if suffix.is_empty() || suffix.len() > 33 || !suffix.chars().all(|c| ALPHABET.contains(c)) {
+            return Err(SignerError::InvalidVanityPattern);
+        }
+        Ok(Pattern::Suffix(suffix.to_owned()))
+    }
+
+    /// Regular expression pattern
+    pub fn regex(regex: &str) -> Result<Self, SignerError> {
+        regex::Regex::new(regex)
+            .map(Pattern::Regex)
+            .map_err(|_| SignerError::InvalidVanityPattern)
+    }
+
+    /// Check base58 address
+    pub fn matches(&self, address: &str) -> bool {
+        match self {
+            Pattern::Prefix(prefix) => address.starts_with(prefix.as_str()),
+            Pattern::Suffix(suffix) => address.ends_with(suffix.as_str()),
+            Pattern::Regex(regex) => regex.is_match(address),
+        }
+    }
+
+    /// Expected number of keys to generate for one match (unknown for regex)
+    pub fn difficulty(&self) -> Option<f64> {
+        match self {
+            Pattern::Prefix(prefix) if prefix.len() > 1 => {
+                let (min, max) = second_chars();
+                // characters are uniform except for the second one, which is limited by the 0x41 version byte
+                Some((max - min + 1) as f64 * 58f64.powi(prefix.len() as i32 - 2))
+            }
+            Pattern::Prefix(_) => Some(1.0),
+            Pattern::Suffix(suffix) => Some(58f64.powi(suffix.len() as i32)),
+            Pattern::Regex(_) => None,
+        }
+    }
+}
+
+impl core::str::FromStr for Pattern {
+    type Err = SignerError;
+
+    /// Parse `prefix:...`, `suffix:...` or `regex:...`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            Some(("prefix", prefix)) => Pattern::prefix(prefix),
+            Some(("suffix", suffix)) => Pattern::suffix(suffix),
+            Some(("regex", regex)) => Pattern::regex(regex),
+            _ => Err(SignerError::InvalidVanityPattern),
+        }
+    }
+}
+
+/// Search progress
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Progress {
+    /// Keys generated
+    pub attempts: u64,
+    /// Time since search start
+    pub elapsed: Duration,
+    /// Expected number of keys for one match (see [`Pattern::difficulty`])
+    pub difficulty: Option<f64>,
+}
+
+impl Progress {
+    /// Keys per second
+    pub fn rate(&self) -> f64 {
+        self.attempts as f64 / self.elapsed.as_secs_f64().max(f64::EPSILON)
+    }
+
+    /// Probability to have found a match with this many attempts
+    pub fn probability(&self) -> Option<f64> {
+        self.difficulty
+            .map(|d| -(self.attempts as f64 * (-1.0 / d).ln_1p()).exp_m1())
+    }
+
+    /// Expected search time at current rate (searches are memoryless: this is also expected remaining time)
+    pub fn expected_time(&self) -> Option<Duration> {
+        let rate = self.rate();
+        self.difficulty
+            .filter(|_| rate > 0.0)
+            .map(|d| Duration::from_secs_f64(d / rate))
+    }
+}
+
+/// Multi-threaded vanity address search
+#[derive(Debug, Clone)]
+pub struct VanitySearch {
+    pattern: Pattern,
+    threads: usize,
+    progress_interval: Duration,
+}
+
+impl VanitySearch {
+    /// Search on all CPU cores, report progress every second
+    pub fn new(pattern: Pattern) -> Self {
+        Self {
+            pattern,
+            threads: thread::available_parallelism().map_or(1, |n| n.get()),
+            progress_interval: Duration::from_secs(1),
+        }
+    }
+
+    /// Set number of worker threads
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        self.threads = threads.max(1);
+        self
+    }
+
+    /// Set progress reporting interval
+    pub fn with_progress_interval(mut self, interval: Duration) -> Self {
+        self.progress_interval = interval;
+        self
+    }
+
+    /// Get pattern
+    pub fn pattern(&self) -> &Pattern {
+        &self.pattern
+    }
+
+    /// Generate keypairs until address matches pattern.
+    /// Returns `None` if `cancel` was set before a match was found.
+    pub fn run(
+        &self,
+        cancel: &AtomicBool,
+        mut on_progress: impl FnMut(&Progress),
+    ) -> Option<Keypair> {
+        let start = Instant::now();
+        let attempts = AtomicU64::new(0);
+        let done = AtomicBool::new(false);
+        let (sender, receiver) = mpsc::channel();
+        thread::scope(|scope| {
+            for _ in 0..self.threads {
+                let sender = sender.clone();
+                let (attempts, done) = (&attempts, &done);
+                scope.spawn(move || {
+                    while !done.load(Ordering::Relaxed) && !cancel.load(Ordering::Relaxed) {
+                        for i in 1..=BATCH {
+                            let keypair = Keypair::generate(&mut OsRng);
+                            if self.pattern.matches(&keypair.address().as_base58()) {
+                                attempts.fetch_add(i, Ordering::Relaxed);
+                                done.store(true, Ordering::Relaxed);
+                                let _ = sender.send(keypair);
+                                return;
+                            }
+                        }
+                        attempts.fetch_add(BATCH, Ordering::Relaxed);
+                    }
+                });
+            }
+            drop(sender);
+            let progress = || Progress {
+                attempts: attempts.load(Ordering::Relaxed),
+                elapsed: start.elapsed(),
+                difficulty: self.pattern.difficulty(),
+            };
+            let found = loop {
+                match receiver.recv_timeout(self.progress_interval) {
+                    Ok(keypair) => break Some(keypair),
+                    Err(RecvTimeoutError::Timeout) => on_progress(&progress()),
+                    // all workers stopped by cancellation
+                    Err(RecvTimeoutError::Disconnected) => break None,
+                }
+            };
+            done.store(true, Ordering::Relaxed);
+            on_progress(&progress());
+            found
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_patterns() {
+        assert_eq!(
+            second_chars(),
+            (ALPHABET.find('9').unwrap(), ALPHABET.find('Z').unwrap())
+        );
+        assert!(Pattern::prefix("TAB").is_ok());
+        assert!(Pattern::prefix(&"T".repeat(34)).is_ok());
+        assert!(Pattern::prefix(&"T".repeat(35)).is_err());
+        for invalid in ["", "A", "T0", "Ta", "T8"] {
+            assert!(Pattern::prefix(invalid).is_err(), "{}", invalid);
+        }
+        assert!(Pattern::suffix("abc").is_ok());
+        assert!(Pattern::suffix("l").is_err());
+        assert!(Pattern::regex("(").is_err());
+        assert!(matches!("suffix:xyz".parse(), Ok(Pattern::Suffix(s)) if s == "xyz"));
+        assert!("xyz".parse::<Pattern>().is_err());
+
+        assert_eq!(Pattern::prefix("T").unwrap().difficulty(), Some(1.0));
+        assert_eq!(Pattern::prefix("TA").unwrap().difficulty(), Some(25.0));
+        assert_eq!(
+            Pattern::prefix("TAB").unwrap().difficulty(),
+            Some(25.0 * 58.0)
+        );
+        assert_eq!(
+            Pattern::suffix("ab").unwrap().difficulty(),
+            Some(58.0 * 58.0)
+        );
+        assert_eq!(Pattern::regex("^T").unwrap().difficulty(), None);
+
+        let progress = Progress {
+            attempts: 58,
+            elapsed: Duration::from_secs(2),
+            difficulty: Some(58.0),
+        };
+        assert_eq!(progress.rate(), 29.0);
+        assert!((progress.probability().unwrap() - 0.6353).abs() < 1e-3);
+        assert_eq!(progress.expected_time(), Some(Duration::from_secs(2)));
+    }
+
+    #[test]
+    fn test_search() {
+        let cancel = AtomicBool::new(false);
+        for pattern in ["suffix:a", "prefix:TT", "regex:[0-9]$"] {
+            let pattern: Pattern = pattern.parse().unwrap();
+            let mut reports = 0;
+            let keypair = VanitySearch::new(pattern.clone())
+                .with_threads(2)
+                .run(&cancel, |progress| {
+                    reports += 1;
+                    assert!(progress.attempts > 0);
+                })
+                .unwrap();
+            assert!(pattern.matches(&keypair.address().as_base58()));
+            assert!(reports > 0);
+        }
+    }
+
+    #[test]
+    fn test_cancel() {
+        let cancel = AtomicBool::new(false);
+        let search = VanitySearch::new(Pattern::suffix("zzzzzzzzzz").unwrap())
+            .with_threads(2)
+            .with_progress_interval(Duration::from_millis(10));
+        let mut last = None;
+        let found = search.run(&cancel, |progress| {
+            last = Some(*progress);
+            if progress.elapsed > Duration::from_millis(100) {
+                cancel.store(true, Ordering::Relaxed);
+            }
+        });
+        assert!(found.is_none());
+        assert!(last.unwrap().probability().unwrap() < 1e-6);
+    }
+}
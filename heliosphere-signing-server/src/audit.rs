@@ -0,0 +1,72 @@
+//! Append-only audit log (one JSON entry per line, synced to disk before signing)
+
+use std::{
+    fs::{File, OpenOptions},
+    io::{BufRead, BufReader, Write},
+    path::Path,
+};
+
+use heliosphere_core::{transaction::TransactionId, Address};
+use serde::{Deserialize, Serialize};
+
+use crate::policy::Spend;
+
+/// Audit log entry
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// Unix time in milliseconds
+    pub time: u64,
+    /// Transaction id
+    pub tx_id: TransactionId,
+    /// Contract type
+    #[serde(default)]
+    pub contract: Option<String>,
+    /// Contract owner
+    #[serde(default)]
+    pub owner: Option<Address>,
+    /// Whether transaction was signed
+    pub approved: bool,
+    /// Rejection reason
+    #[serde(default)]
+    pub reason: Option<String>,
+    /// Value moved by signed transaction
+    #[serde(default)]
+    pub spend: Option<Spend>,
+}
+
+/// Append-only audit log file
+#[derive(Debug)]
+pub struct AuditLog {
+    file: File,
+}
+
+impl AuditLog {
+    /// Open (or create) audit log, returns log and existing entries
+    pub fn open(path: impl AsRef<Path>) -> std::io::Result<(Self, Vec<AuditEntry>)> {
+        let file = OpenOptions::new()
+            .read(true)
+            .append(true)
+            .create(true)
+            .open(path)?;
+        let mut entries = Vec::new();
+        for line in BufReader::new(&file).lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            entries.push(
+                serde_json::from_str(&line)
+                    .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?,
+            );
+        }
+        Ok((Self { file }, entries))
+    }
+
+    /// Append entry and sync it to disk
+    pub fn append(&mut self, entry: &AuditEntry) -> std::io::Result<()> {
+        let mut line = serde_json::to_vec(entry)?;
+        line.push(b'\n');
+        self.file.write_all(&line)?;
+        self.file.sync_data()
+    }
+}
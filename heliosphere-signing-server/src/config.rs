@@ -0,0 +1,84 @@
+//! Server configuration file (TOML)
+
+use std::{net::SocketAddr, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::policy::PolicyConfig;
+
+/// Server configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ServerConfig {
+    /// Listen address
+    #[serde(default = "default_listen")]
+    pub listen: SocketAddr,
+    /// Audit log path
+    pub audit_log: PathBuf,
+    /// Environment variable holding bearer token clients must present (no auth if not set)
+    #[serde(default)]
+    pub auth_token_env: Option<String>,
+    /// Signing key
+    pub key: KeyConfig,
+    /// Signing policy
+    #[serde(default)]
+    pub policy: PolicyConfig,
+}
+
+/// Signing key location
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct KeyConfig {
+    /// Encrypted keystore (Web3 Secret Storage v3) file
+    pub keystore: PathBuf,
+    /// Environment variable holding keystore password
+    #[serde(default = "default_password_env")]
+    pub password_env: String,
+}
+
+fn default_listen() -> SocketAddr {
+    ([127, 0, 0, 1], 8645).into()
+}
+
+fn default_password_env() -> String {
+    "KEYSTORE_PASSWORD".to_owned()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let config: ServerConfig = toml::from_str(
+            r#"
+            audit_log = "audit.jsonl"
+
+            [key]
+            keystore = "key.json"
+
+            [policy]
+            allowed_contracts = ["TransferContract", "TriggerSmartContract"]
+            allowed_destinations = ["TNPeeaaFB7K9cmo4uQpcU32zGK8G1NYqeL"]
+            trc20_contracts = ["TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t"]
+            max_fee_limit = 100000000
+            trx_limit = 1000000000
+
+            [policy.token_limits]
+            TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t = "1000000000000000000000000"
+
+            [policy.trc10_limits]
+            1002000 = 1000000
+            "#,
+        )
+        .unwrap();
+        assert_eq!(config.listen, default_listen());
+        assert_eq!(config.key.password_env, "KEYSTORE_PASSWORD");
+        assert_eq!(config.policy.period_secs, 86400);
+        assert_eq!(
+            config.policy.token_limits.values().next(),
+            Some(&1_000_000_000_000_000_000_000_000)
+        );
+        assert_eq!(config.policy.trc10_limits["1002000"], 1_000_000);
+    }
+}
@@ -0,0 +1,10 @@
+//! Policy-enforcing [heliosphere](https://crates.io/crates/heliosphere) signing server
+//!
+//! Holds the signing key in one hardened process and signs only transactions that pass policy checks.
+#![forbid(unsafe_code)]
+#![deny(missing_docs)]
+
+pub mod audit;
+pub mod config;
+pub mod policy;
+pub mod server;
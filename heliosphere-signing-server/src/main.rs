@@ -0,0 +1,37 @@
+//! `heliosphere-signing-server <config.toml>`
+
+use heliosphere_signer::keypair::Keypair;
+use heliosphere_signing_server::{
+    audit::AuditLog, config::ServerConfig, policy::Policy, server::SigningServer,
+};
+
+fn env(name: &str) -> Result<String, Box<dyn std::error::Error>> {
+    std::env::var(name).map_err(|_| format!("environment variable {} is not set", name).into())
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let path = std::env::args()
+        .nth(1)
+        .ok_or("usage: heliosphere-signing-server <config.toml>")?;
+    let config: ServerConfig = toml::from_str(&std::fs::read_to_string(path)?)?;
+
+    let keypair = Keypair::from_keystore(
+        &std::fs::read_to_string(&config.key.keystore)?,
+        &env(&config.key.password_env)?,
+    )?;
+    let (audit_log, history) = AuditLog::open(&config.audit_log)?;
+    let mut server = SigningServer::new(keypair, Policy::new(config.policy), audit_log, &history);
+    if let Some(name) = &config.auth_token_env {
+        server = server.with_auth_token(env(name)?);
+    }
+
+    let listener = tokio::net::TcpListener::bind(config.listen).await?;
+    eprintln!("listening on {}", listener.local_addr()?);
+    axum::serve(listener, server.into_router())
+        .with_graceful_shutdown(async {
+            let _ = tokio::signal::ctrl_c().await;
+        })
+        .await?;
+    Ok(())
+}
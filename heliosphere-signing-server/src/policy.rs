@@ -0,0 +1,670 @@
+//! Signing policy: which transactions may be signed and how much may be spent per period
+
+use std::{
+    collections::{BTreeMap, BTreeSet, VecDeque},
+    time::Duration,
+};
+
+use heliosphere_core::{
+    contract::ContractKind,
+    transaction::{RawTxData, Transaction},
+    Address,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::audit::AuditEntry;
+
+const TRC20_TRANSFER: [u8; 4] = [0xa9, 0x05, 0x9c, 0xbb];
+const TRC20_APPROVE: [u8; 4] = [0x09, 0x5e, 0xa7, 0xb3];
+const TRC20_TRANSFER_FROM: [u8; 4] = [0x23, 0xb8, 0x72, 0xdd];
+
+/// Policy violation
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum PolicyError {
+    /// Transaction raw data can't be decoded or does not match tx id
+    #[error("invalid transaction: {0}")]
+    InvalidTransaction(String),
+    /// Transaction must contain exactly one contract
+    #[error("transaction must contain exactly one contract")]
+    ContractCount,
+    /// Contract type is not allowed
+    #[error("contract type {0} is not allowed")]
+    ContractNotAllowed(String),
+    /// Contract type is not understood by policy (its receivers can't be checked)
+    #[error("contract type {0} is not supported by policy")]
+    ContractNotSupported(String),
+    /// Contract owner is neither the signer nor an allowed owner
+    #[error("owner {0} is not allowed")]
+    OwnerNotAllowed(Address),
+    /// Destination address is not allowed
+    #[error("destination {0} is not allowed")]
+    DestinationNotAllowed(Address),
+    /// Smart contract is not an allowed TRC20 token
+    #[error("smart contract {0} is not allowed")]
+    SmartContractNotAllowed(Address),
+    /// TRC20 method is not transfer, approve or transferFrom
+    #[error("TRC20 method is not allowed")]
+    MethodNotAllowed,
+    /// Fee limit is above maximum
+    #[error("fee limit {0} is above maximum")]
+    FeeLimitTooHigh(u64),
+    /// Transaction already expired
+    #[error("transaction expired")]
+    Expired,
+    /// TRX spend limit of the period would be exceeded
+    #[error("TRX spend limit exceeded")]
+    TrxLimitExceeded,
+    /// Token spend limit of the period would be exceeded
+    #[error("token {0} spend limit exceeded")]
+    TokenLimitExceeded(Address),
+    /// TRC10 asset has no spend limit configured
+    #[error("TRC10 asset {0} is not allowed")]
+    AssetNotAllowed(String),
+    /// TRC10 spend limit of the period would be exceeded
+    #[error("TRC10 asset {0} spend limit exceeded")]
+    AssetLimitExceeded(String),
+}
+
+/// Policy configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct PolicyConfig {
+    /// Allowed contract types (java-tron names, e.g. `TransferContract`), types not supported by
+    /// policy are always rejected
+    #[serde(default = "default_allowed_contracts")]
+    pub allowed_contracts: BTreeSet<String>,
+    /// Accounts that may own signed contracts (e.g. multi-signature accounts), only the signer if not set
+    #[serde(default)]
+    pub allowed_owners: Option<BTreeSet<Address>>,
+    /// Allowed TRX, TRC10 and TRC20 recipients (any if not set)
+    #[serde(default)]
+    pub allowed_destinations: Option<BTreeSet<Address>>,
+    /// Smart contracts that may be triggered, as TRC20 tokens
+    #[serde(default)]
+    pub trc20_contracts: BTreeSet<Address>,
+    /// Maximum transaction fee limit in SUN
+    #[serde(default)]
+    pub max_fee_limit: Option<u64>,
+    /// Spend limit period in seconds
+    #[serde(default = "default_period_secs")]
+    pub period_secs: u64,
+    /// TRX spend limit per period in SUN (transfers and call values)
+    #[serde(default)]
+    pub trx_limit: Option<u64>,
+    /// TRC20 spend limits per period in token base units (transfers and approvals),
+    /// values may be strings to exceed TOML integer range
+    #[serde(default, deserialize_with = "de_token_limits")]
+    pub token_limits: BTreeMap<Address, u128>,
+    /// TRC10 spend limits per period by asset id (transfers and call token values),
+    /// assets without limit are rejected
+    #[serde(default)]
+    pub trc10_limits: BTreeMap<String, u64>,
+}
+
+fn de_token_limits<'de, D>(deserializer: D) -> Result<BTreeMap<Address, u128>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Amount {
+        Int(u64),
+        Str(String),
+    }
+    BTreeMap::<Address, Amount>::deserialize(deserializer)?
+        .into_iter()
+        .map(|(token, amount)| match amount {
+            Amount::Int(n) => Ok((token, n as u128)),
+            Amount::Str(s) => s
+                .parse()
+                .map(|n| (token, n))
+                .map_err(serde::de::Error::custom),
+        })
+        .collect()
+}
+
+fn default_allowed_contracts() -> BTreeSet<String> {
+    BTreeSet::from(["TransferContract".to_owned()])
+}
+
+fn default_period_secs() -> u64 {
+    86400
+}
+
+impl Default for PolicyConfig {
+    fn default() -> Self {
+        Self {
+            allowed_contracts: default_allowed_contracts(),
+            allowed_owners: None,
+            allowed_destinations: None,
+            trc20_contracts: BTreeSet::new(),
+            max_fee_limit: None,
+            period_secs: default_period_secs(),
+            trx_limit: None,
+            token_limits: BTreeMap::new(),
+            trc10_limits: BTreeMap::new(),
+        }
+    }
+}
+
+/// Value moved by a transaction
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Spend {
+    /// TRX in SUN
+    #[serde(default)]
+    pub trx: u128,
+    /// TRC20 tokens in base units
+    #[serde(default)]
+    pub tokens: BTreeMap<Address, u128>,
+    /// TRC10 assets by asset id
+    #[serde(default)]
+    pub assets: BTreeMap<String, u128>,
+}
+
+/// Policy with spend accounting
+#[derive(Debug)]
+pub struct Policy {
+    config: PolicyConfig,
+    signer: Option<Address>,
+    history: VecDeque<(u64, Spend)>,
+}
+
+impl Policy {
+    /// Create policy with empty spend history
+    pub fn new(config: PolicyConfig) -> Self {
+        Self {
+            config,
+            signer: None,
+            history: VecDeque::new(),
+        }
+    }
+
+    /// Set signer address, contracts owned by it are allowed if `allowed_owners` is not configured
+    pub fn with_signer(mut self, address: Address) -> Self {
+        self.signer = Some(address);
+        self
+    }
+
+    /// Get configuration
+    pub fn config(&self) -> &PolicyConfig {
+        &self.config
+    }
+
+    fn period(&self) -> Duration {
+        Duration::from_secs(self.config.period_secs)
+    }
+
+    /// Restore spend history from audit log entries
+    pub fn replay<'a>(&mut self, entries: impl IntoIterator<Item = &'a AuditEntry>, now_ms: u64) {
+        let since = now_ms.saturating_sub(self.period().as_millis() as u64);
+        for entry in entries {
+            if let (true, Some(spend)) = (entry.approved && entry.time >= since, &entry.spend) {
+                self.history.push_back((entry.time, spend.clone()));
+            }
+        }
+    }
+
+    fn spent(&mut self, now_ms: u64) -> Spend {
+        let since = now_ms.saturating_sub(self.period().as_millis() as u64);
+        while matches!(self.history.front(), Some((time, _)) if *time < since) {
+            self.history.pop_front();
+        }
+        let mut total = Spend::default();
+        for (_, spend) in &self.history {
+            total.trx = total.trx.saturating_add(spend.trx);
+            for (token, amount) in &spend.tokens {
+                let sum = total.tokens.entry(*token).or_default();
+                *sum = sum.saturating_add(*amount);
+            }
+            for (asset, amount) in &spend.assets {
+                let sum = total.assets.entry(asset.clone()).or_default();
+                *sum = sum.saturating_add(*amount);
+            }
+        }
+        total
+    }
+
+    /// Decode transaction and check it against policy, returns decoded raw data and value it moves
+    pub fn check(
+        &mut self,
+        tx: &Transaction,
+        now_ms: u64,
+    ) -> Result<(RawTxData, Spend), PolicyError> {
+        let raw_data = tx
+            .decode_raw_data()
+            .map_err(|e| PolicyError::InvalidTransaction(format!("{:?}", e)))?;
+        let [contract] = raw_data.contract.as_slice() else {
+            return Err(PolicyError::ContractCount);
+        };
+        let kind = &contract.kind;
+        if !self.config.allowed_contracts.contains(kind.type_name()) {
+            return Err(PolicyError::ContractNotAllowed(kind.type_name().to_owned()));
+        }
+        let owner = kind
+            .owner_address()
+            .ok_or_else(|| PolicyError::ContractNotSupported(kind.type_name().to_owned()))?;
+        let owner_allowed = match &self.config.allowed_owners {
+            Some(allowed) => allowed.contains(&owner),
+            None => self.signer == Some(owner),
+        };
+        if !owner_allowed {
+            return Err(PolicyError::OwnerNotAllowed(owner));
+        }
+        if raw_data.expiration < now_ms {
+            return Err(PolicyError::Expired);
+        }
+        if let Some(max) = self.config.max_fee_limit {
+            if raw_data.fee_limit > max {
+                return Err(PolicyError::FeeLimitTooHigh(raw_data.fee_limit));
+            }
+        }
+        let spend = self.contract_spend(kind)?;
+
+        let spent = self.spent(now_ms);
+        if let Some(limit) = self.config.trx_limit {
+            if spent.trx.saturating_add(spend.trx) > limit as u128 {
+                return Err(PolicyError::TrxLimitExceeded);
+            }
+        }
+        for (token, amount) in &spend.tokens {
+            if let Some(limit) = self.config.token_limits.get(token) {
+                let spent = spent.tokens.get(token).copied().unwrap_or_default();
+                if spent.saturating_add(*amount) > *limit {
+                    return Err(PolicyError::TokenLimitExceeded(*token));
+                }
+            }
+        }
+        for (asset, amount) in &spend.assets {
+            let limit = self.config.trc10_limits[asset];
+            let spent = spent.assets.get(asset).copied().unwrap_or_default();
+            if spent.saturating_add(*amount) > limit as u128 {
+                return Err(PolicyError::AssetLimitExceeded(asset.clone()));
+            }
+        }
+        Ok((raw_data, spend))
+    }
+
+    /// Account approved spend
+    pub fn record(&mut self, spend: Spend, now_ms: u64) {
+        self.history.push_back((now_ms, spend));
+    }
+
+    fn check_destination(&self, address: &Address) -> Result<(), PolicyError> {
+        match &self.config.allowed_destinations {
+            Some(allowed) if !allowed.contains(address) => {
+                Err(PolicyError::DestinationNotAllowed(*address))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn check_asset(&self, asset: String) -> Result<String, PolicyError> {
+        if self.config.trc10_limits.contains_key(&asset) {
+            Ok(asset)
+        } else {
+            Err(PolicyError::AssetNotAllowed(asset))
+        }
+    }
+
+    fn contract_spend(&self, kind: &ContractKind) -> Result<Spend, PolicyError> {
+        let mut spend = Spend::default();
+        match kind {
+            ContractKind::TransferContract(c) => {
+                self.check_destination(&c.to_address)?;
+                spend.trx = c.amount.max(0) as u128;
+            }
+            ContractKind::TransferAssetContract(c) => {
+                self.check_destination(&c.to_address)?;
+                let asset =
+                    self.check_asset(String::from_utf8_lossy(&c.asset_name).into_owned())?;
+                spend.assets.insert(asset, c.amount.max(0) as u128);
+            }
+            ContractKind::TriggerSmartContract(c) => {
+                if !self.config.trc20_contracts.contains(&c.contract_address) {
+                    return Err(PolicyError::SmartContractNotAllowed(c.contract_address));
+                }
+                spend.trx = c.call_value.max(0) as u128;
+                if c.call_token_value > 0 {
+                    let asset = self.check_asset(c.token_id.to_string())?;
+                    spend.assets.insert(asset, c.call_token_value as u128);
+                }
+                let (to, amount) = decode_trc20_call(&c.data)?;
+                self.check_destination(&to)?;
+                spend.tokens.insert(c.contract_address, amount);
+            }
+            ContractKind::DelegateResourceContract(c) => {
+                self.check_destination(&c.receiver_address)?;
+            }
+            // value stays with owner
+            ContractKind::FreezeBalanceV2Contract(_)
+            | ContractKind::UnfreezeBalanceV2Contract(_)
+            | ContractKind::WithdrawExpireUnfreezeContract(_)
+            | ContractKind::CancelAllUnfreezeV2Contract(_)
+            | ContractKind::WithdrawBalanceContract(_)
+            | ContractKind::UnDelegateResourceContract(_) => {}
+            _ => {
+                return Err(PolicyError::ContractNotSupported(
+                    kind.type_name().to_owned(),
+                ))
+            }
+        }
+        Ok(spend)
+    }
+}
+
+/// Decode recipient (or spender) and amount of TRC20 transfer, approve or transferFrom call
+fn decode_trc20_call(data: &[u8]) -> Result<(Address, u128), PolicyError> {
+    let (selector, args) = data
+        .split_at_checked(4)
+        .ok_or(PolicyError::MethodNotAllowed)?;
+    let args = match selector.try_into().expect("Always 4 bytes") {
+        TRC20_TRANSFER | TRC20_APPROVE if args.len() == 64 => args,
+        TRC20_TRANSFER_FROM if args.len() == 96 => &args[32..],
+        _ => return Err(PolicyError::MethodNotAllowed),
+    };
+    let (to, amount) = args.split_at(32);
+    if to[..12].iter().any(|b| *b != 0) || amount[..16].iter().any(|b| *b != 0) {
+        return Err(PolicyError::MethodNotAllowed);
+    }
+    let mut address = [0x41; 21];
+    address[1..].copy_from_slice(&to[12..]);
+    Ok((
+        Address::new(address).expect("Has 0x41 prefix"),
+        u128::from_be_bytes(amount[16..].try_into().expect("Always 16 bytes")),
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use heliosphere_core::{
+        block::BlockId,
+        contract::{
+            AccountPermissionUpdateContract, DelegateResourceContract, FreezeBalanceV2Contract,
+            ResourceType, TransferAssetContract, TransferContract, TriggerSmartContract,
+        },
+        transaction::TransactionBuilder,
+        Sun,
+    };
+
+    use super::*;
+
+    fn address(n: u8) -> Address {
+        Address::new([
+            0x41, n, n, n, n, n, n, n, n, n, n, n, n, n, n, n, n, n, n, n, n,
+        ])
+        .unwrap()
+    }
+
+    fn build(contract: impl Into<ContractKind>, fee_limit: u64) -> Transaction {
+        TransactionBuilder::new(contract)
+            .with_ref_block_id(BlockId([0x01; 32]))
+            .with_timestamp(1_000)
+            .with_fee_limit(Sun::new(fee_limit))
+            .build()
+            .unwrap()
+    }
+
+    fn trx(to: Address, amount: i64) -> Transaction {
+        build(
+            TransferContract {
+                owner_address: address(1),
+                to_address: to,
+                amount,
+            },
+            0,
+        )
+    }
+
+    fn trc20_call(
+        token: Address,
+        selector: [u8; 4],
+        to: Address,
+        amount: u128,
+    ) -> TriggerSmartContract {
+        let mut data = selector.to_vec();
+        data.extend_from_slice(&[0; 12]);
+        data.extend_from_slice(&to.as_bytes()[1..]);
+        data.extend_from_slice(&[0; 16]);
+        data.extend_from_slice(&amount.to_be_bytes());
+        TriggerSmartContract {
+            owner_address: address(1),
+            contract_address: token,
+            call_value: 0,
+            data,
+            call_token_value: 0,
+            token_id: 0,
+        }
+    }
+
+    fn trc20(token: Address, selector: [u8; 4], to: Address, amount: u128) -> Transaction {
+        build(trc20_call(token, selector, to, amount), 50_000_000)
+    }
+
+    fn trc10(asset: &str, to: Address, amount: i64) -> Transaction {
+        build(
+            TransferAssetContract {
+                asset_name: asset.as_bytes().to_vec(),
+                owner_address: address(1),
+                to_address: to,
+                amount,
+            },
+            0,
+        )
+    }
+
+    fn policy() -> Policy {
+        Policy::new(PolicyConfig {
+            allowed_contracts: BTreeSet::from([
+                "TransferContract".to_owned(),
+                "TransferAssetContract".to_owned(),
+                "TriggerSmartContract".to_owned(),
+                "DelegateResourceContract".to_owned(),
+                "AccountPermissionUpdateContract".to_owned(),
+            ]),
+            allowed_owners: None,
+            allowed_destinations: Some(BTreeSet::from([address(2)])),
+            trc20_contracts: BTreeSet::from([address(9)]),
+            max_fee_limit: Some(100_000_000),
+            period_secs: 10,
+            trx_limit: Some(1_000),
+            token_limits: BTreeMap::from([(address(9), 500)]),
+            trc10_limits: BTreeMap::from([("1002000".to_owned(), 300)]),
+        })
+        .with_signer(address(1))
+    }
+
+    #[test]
+    fn test_trx_limit() {
+        let mut policy = policy();
+        let (_, spend) = policy.check(&trx(address(2), 600), 2_000).unwrap();
+        assert_eq!(spend.trx, 600);
+        policy.record(spend, 2_000);
+        assert_eq!(
+            policy.check(&trx(address(2), 600), 3_000),
+            Err(PolicyError::TrxLimitExceeded)
+        );
+        // spend of 2_000 leaves the 10s window
+        assert!(policy.check(&trx(address(2), 600), 12_001).is_ok());
+        assert_eq!(
+            policy.check(&trx(address(3), 1), 3_000),
+            Err(PolicyError::DestinationNotAllowed(address(3)))
+        );
+    }
+
+    #[test]
+    fn test_trc20() {
+        let mut policy = policy();
+        let (_, spend) = policy
+            .check(&trc20(address(9), TRC20_TRANSFER, address(2), 400), 2_000)
+            .unwrap();
+        assert_eq!(spend.tokens[&address(9)], 400);
+        policy.record(spend, 2_000);
+        assert_eq!(
+            policy.check(&trc20(address(9), TRC20_APPROVE, address(2), 101), 2_000),
+            Err(PolicyError::TokenLimitExceeded(address(9)))
+        );
+        assert_eq!(
+            policy.check(&trc20(address(8), TRC20_TRANSFER, address(2), 1), 2_000),
+            Err(PolicyError::SmartContractNotAllowed(address(8)))
+        );
+        assert_eq!(
+            policy.check(
+                &trc20(address(9), [0xde, 0xad, 0xbe, 0xef], address(2), 1),
+                2_000
+            ),
+            Err(PolicyError::MethodNotAllowed)
+        );
+        assert_eq!(
+            policy.check(&trc20(address(9), TRC20_TRANSFER, address(3), 1), 2_000),
+            Err(PolicyError::DestinationNotAllowed(address(3)))
+        );
+    }
+
+    #[test]
+    fn test_trc10() {
+        let mut policy = policy();
+        let (_, spend) = policy
+            .check(&trc10("1002000", address(2), 200), 2_000)
+            .unwrap();
+        assert_eq!(spend.assets["1002000"], 200);
+        policy.record(spend, 2_000);
+        assert_eq!(
+            policy.check(&trc10("1002000", address(2), 101), 2_000),
+            Err(PolicyError::AssetLimitExceeded("1002000".into()))
+        );
+        assert_eq!(
+            policy.check(&trc10("1002000", address(3), 1), 2_000),
+            Err(PolicyError::DestinationNotAllowed(address(3)))
+        );
+        let mut call = trc20_call(address(9), TRC20_TRANSFER, address(2), 1);
+        call.call_token_value = 101;
+        call.token_id = 1002000;
+        assert_eq!(
+            policy.check(&build(call, 50_000_000), 2_000),
+            Err(PolicyError::AssetLimitExceeded("1002000".into()))
+        );
+    }
+
+    #[test]
+    fn test_contract_types() {
+        let mut policy = policy();
+        let delegate = |receiver| {
+            build(
+                DelegateResourceContract {
+                    owner_address: address(1),
+                    resource: ResourceType::Energy,
+                    balance: 1_000_000,
+                    receiver_address: receiver,
+                    lock: false,
+                    lock_period: 0,
+                },
+                0,
+            )
+        };
+        let (_, spend) = policy.check(&delegate(address(2)), 2_000).unwrap();
+        assert_eq!(spend, Spend::default());
+        assert_eq!(
+            policy.check(&delegate(address(3)), 2_000),
+            Err(PolicyError::DestinationNotAllowed(address(3)))
+        );
+        let permission_update = build(
+            AccountPermissionUpdateContract {
+                owner_address: address(1),
+                owner: None,
+                witness: None,
+                actives: vec![],
+            },
+            0,
+        );
+        assert_eq!(
+            policy.check(&permission_update, 2_000),
+            Err(PolicyError::ContractNotSupported(
+                "AccountPermissionUpdateContract".into()
+            ))
+        );
+    }
+
+    #[test]
+    fn test_owners() {
+        let transfer_from = |owner| {
+            build(
+                TransferContract {
+                    owner_address: owner,
+                    to_address: address(2),
+                    amount: 1,
+                },
+                0,
+            )
+        };
+        let mut policy = policy();
+        assert!(policy.check(&transfer_from(address(1)), 2_000).is_ok());
+        assert_eq!(
+            policy.check(&transfer_from(address(5)), 2_000),
+            Err(PolicyError::OwnerNotAllowed(address(5)))
+        );
+
+        // multi-signature account
+        policy.config.allowed_owners = Some(BTreeSet::from([address(5)]));
+        assert!(policy.check(&transfer_from(address(5)), 2_000).is_ok());
+        assert_eq!(
+            policy.check(&transfer_from(address(1)), 2_000),
+            Err(PolicyError::OwnerNotAllowed(address(1)))
+        );
+
+        // fail closed without signer
+        let mut policy = Policy::new(policy.config.clone());
+        policy.config.allowed_owners = None;
+        assert_eq!(
+            policy.check(&transfer_from(address(1)), 2_000),
+            Err(PolicyError::OwnerNotAllowed(address(1)))
+        );
+    }
+
+    #[test]
+    fn test_rejections() {
+        let mut policy = policy();
+        let freeze = build(
+            FreezeBalanceV2Contract {
+                owner_address: address(1),
+                frozen_balance: 1,
+                resource: ResourceType::Energy,
+            },
+            0,
+        );
+        assert_eq!(
+            policy.check(&freeze, 2_000),
+            Err(PolicyError::ContractNotAllowed(
+                "FreezeBalanceV2Contract".into()
+            ))
+        );
+        assert_eq!(
+            policy.check(&trx(address(2), 1), 61_001),
+            Err(PolicyError::Expired)
+        );
+        let mut tx = trc20(address(9), TRC20_TRANSFER, address(2), 1);
+        assert!(policy.check(&tx, 2_000).is_ok());
+        tx.raw_data_bytes[0] ^= 1;
+        assert!(matches!(
+            policy.check(&tx, 2_000),
+            Err(PolicyError::InvalidTransaction(_))
+        ));
+        policy.config.max_fee_limit = Some(1);
+        assert_eq!(
+            policy.check(&trc20(address(9), TRC20_TRANSFER, address(2), 1), 2_000),
+            Err(PolicyError::FeeLimitTooHigh(50_000_000))
+        );
+        policy.config.max_fee_limit = None;
+        policy.config.trc10_limits.clear();
+        assert_eq!(
+            policy.check(&trc10("1002000", address(2), 1), 2_000),
+            Err(PolicyError::AssetNotAllowed("1002000".into()))
+        );
+        let mut call = trc20_call(address(9), TRC20_TRANSFER, address(2), 1);
+        call.call_token_value = 1;
+        call.token_id = 1002000;
+        assert_eq!(
+            policy.check(&build(call, 50_000_000), 2_000),
+            Err(PolicyError::AssetNotAllowed("1002000".into()))
+        );
+    }
+}
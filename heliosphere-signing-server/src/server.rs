@@ -0,0 +1,263 @@
+//! HTTP/JSON signing API (the protocol spoken by `heliosphere::remote_signer::RemoteSigner`)
+
+use std::{
+    sync::{Arc, Mutex},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use axum::{
+    extract::State,
+    http::{HeaderMap, StatusCode},
+    routing::{get, post},
+    Json, Router,
+};
+use heliosphere::remote_signer::{
+    ErrorResponse, PublicKeyResponse, SignTransactionRequest, SignatureResponse,
+};
+use heliosphere_core::transaction::Transaction;
+use heliosphere_signer::signer::Signer;
+use subtle::ConstantTimeEq;
+
+use crate::{
+    audit::{AuditEntry, AuditLog},
+    policy::Policy,
+};
+
+type Reply<T> = Result<Json<T>, (StatusCode, Json<ErrorResponse>)>;
+
+fn reject(status: StatusCode, error: impl ToString) -> (StatusCode, Json<ErrorResponse>) {
+    (
+        status,
+        Json(ErrorResponse {
+            error: error.to_string(),
+        }),
+    )
+}
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("Time went backwards")
+        .as_millis() as u64
+}
+
+/// Signing server state
+pub struct SigningServer<S> {
+    signer: S,
+    auth_token: Option<String>,
+    // policy and audit log are locked together so that check, accounting and logging are atomic
+    guarded: Mutex<(Policy, AuditLog)>,
+}
+
+impl<S: Signer + Send + Sync + 'static> SigningServer<S> {
+    /// Create server, spend accounting is restored from audit log entries and contracts
+    /// owned by the signer are allowed
+    pub fn new(signer: S, policy: Policy, audit_log: AuditLog, history: &[AuditEntry]) -> Self {
+        let mut policy = policy.with_signer(signer.address());
+        policy.replay(history, now_ms());
+        Self {
+            signer,
+            auth_token: None,
+            guarded: Mutex::new((policy, audit_log)),
+        }
+    }
+
+    /// Require bearer token on signing requests
+    pub fn with_auth_token(mut self, token: impl Into<String>) -> Self {
+        self.auth_token = Some(token.into());
+        self
+    }
+
+    /// Build axum router
+    pub fn into_router(self) -> Router {
+        Router::new()
+            .route("/public_key", get(public_key::<S>))
+            .route("/sign_transaction", post(sign_transaction::<S>))
+            .route("/sign_prehash", post(sign_prehash))
+            .with_state(Arc::new(self))
+    }
+
+    fn check_and_sign(
+        &self,
+        mut tx: Transaction,
+    ) -> Result<SignatureResponse, (StatusCode, Json<ErrorResponse>)> {
+        tx.signature.clear();
+        let now = now_ms();
+        let mut guarded = self.guarded.lock().expect("Poisoned lock");
+        let (policy, audit_log) = &mut *guarded;
+        let mut entry = AuditEntry {
+            time: now,
+            tx_id: tx.tx_id,
+            contract: None,
+            owner: None,
+            approved: false,
+            reason: None,
+            spend: None,
+        };
+        let spend = match policy.check(&tx, now) {
+            Ok((raw_data, spend)) => {
+                let kind = &raw_data.contract[0].kind;
+                entry.contract = Some(kind.type_name().to_owned());
+                entry.owner = kind.owner_address();
+                spend
+            }
+            Err(e) => {
+                entry.reason = Some(e.to_string());
+                audit_log
+                    .append(&entry)
+                    .map_err(|e| reject(StatusCode::INTERNAL_SERVER_ERROR, e))?;
+                return Err(reject(StatusCode::FORBIDDEN, e));
+            }
+        };
+        entry.approved = true;
+        entry.spend = Some(spend.clone());
+        // fail closed: nothing is signed unless it is in the audit log
+        audit_log
+            .append(&entry)
+            .map_err(|e| reject(StatusCode::INTERNAL_SERVER_ERROR, e))?;
+        policy.record(spend, now);
+        drop(guarded);
+        self.signer
+            .sign_transaction(&mut tx)
+            .map_err(|e| reject(StatusCode::INTERNAL_SERVER_ERROR, format!("{:?}", e)))?;
+        Ok(SignatureResponse {
+            signature: tx.signature[0],
+        })
+    }
+
+    fn authorize(&self, headers: &HeaderMap) -> Result<(), (StatusCode, Json<ErrorResponse>)> {
+        let Some(token) = &self.auth_token else {
+            return Ok(());
+        };
+        let provided = headers
+            .get("authorization")
+            .and_then(|h| h.to_str().ok())
+            .and_then(|h| h.strip_prefix("Bearer "));
+        match provided {
+            Some(provided) if bool::from(provided.as_bytes().ct_eq(token.as_bytes())) => Ok(()),
+            _ => Err(reject(StatusCode::UNAUTHORIZED, "unauthorized")),
+        }
+    }
+}
+
+async fn public_key<S: Signer>(
+    State(server): State<Arc<SigningServer<S>>>,
+) -> Json<PublicKeyResponse> {
+    Json(PublicKeyResponse {
+        public_key: hex::encode(server.signer.public_key().to_sec1_bytes()),
+    })
+}
+
+async fn sign_transaction<S: Signer + Send + Sync + 'static>(
+    State(server): State<Arc<SigningServer<S>>>,
+    headers: HeaderMap,
+    Json(req): Json<SignTransactionRequest>,
+) -> Reply<SignatureResponse> {
+    server.authorize(&headers)?;
+    // audit log is synced to disk and signer may be a hardware token, keep it off async workers
+    tokio::task::spawn_blocking(move || server.check_and_sign(req.transaction))
+        .await
+        .map_err(|e| reject(StatusCode::INTERNAL_SERVER_ERROR, e))?
+        .map(Json)
+}
+
+async fn sign_prehash() -> Reply<SignatureResponse> {
+    Err(reject(
+        StatusCode::FORBIDDEN,
+        "prehash signing is disabled, use sign_transaction",
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::{BTreeMap, BTreeSet};
+
+    use heliosphere::remote_signer::RemoteSigner;
+    use heliosphere_core::{
+        block::BlockId, contract::TransferContract, transaction::TransactionBuilder, Address,
+    };
+    use heliosphere_signer::{async_signer::AsyncSigner, keypair::Keypair, SignedTransaction};
+
+    use super::*;
+    use crate::policy::PolicyConfig;
+
+    fn keypair() -> Keypair {
+        Keypair::from_hex_key("b224f69fe10604d71263971ecd9cdc9f2fc59d0e7ddb6c9df2aa8631423d8cb0")
+            .unwrap()
+    }
+
+    fn transfer(to: Address, amount: i64) -> heliosphere_core::transaction::Transaction {
+        TransactionBuilder::new(TransferContract {
+            owner_address: Signer::address(&keypair()),
+            to_address: to,
+            amount,
+        })
+        .with_ref_block_id(BlockId([0x01; 32]))
+        .with_timestamp(now_ms())
+        .with_memo(amount.to_string())
+        .build()
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_signing_server() {
+        let allowed: Address = "TNPeeaaFB7K9cmo4uQpcU32zGK8G1NYqeL".parse().unwrap();
+        let path = std::env::temp_dir().join(format!("heliosphere-audit-{}.jsonl", now_ms()));
+        let config = PolicyConfig {
+            allowed_destinations: Some(BTreeSet::from([allowed])),
+            trx_limit: Some(1_000),
+            token_limits: BTreeMap::new(),
+            ..Default::default()
+        };
+        let (audit_log, history) = AuditLog::open(&path).unwrap();
+        let server =
+            SigningServer::new(keypair(), Policy::new(config.clone()), audit_log, &history)
+                .with_auth_token("secret");
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, server.into_router()).await.unwrap() });
+
+        let signer = RemoteSigner::new(url.as_str())
+            .unwrap()
+            .with_auth_token("secret");
+        assert_eq!(
+            AsyncSigner::address(&signer).await.unwrap(),
+            Signer::address(&keypair())
+        );
+        let mut tx = transfer(allowed, 600);
+        AsyncSigner::sign_transaction(&signer, &mut tx)
+            .await
+            .unwrap();
+        assert!(tx.verify_signed_by(&Signer::address(&keypair())).unwrap());
+
+        let denied = [
+            (transfer(allowed, 600), "TRX spend limit exceeded"),
+            (transfer(Signer::address(&keypair()), 1), "is not allowed"),
+        ];
+        for (mut tx, error) in denied {
+            match AsyncSigner::sign_transaction(&signer, &mut tx).await {
+                Err(heliosphere::Error::SignerError(e)) => assert!(e.contains(error), "{}", e),
+                other => panic!("unexpected {:?}", other),
+            }
+        }
+        assert!(AsyncSigner::sign_prehash(&signer, &[0; 32]).await.is_err());
+        let unauthorized = RemoteSigner::new(url.as_str())
+            .unwrap()
+            .with_auth_token("secreT");
+        match AsyncSigner::sign_transaction(&unauthorized, &mut transfer(allowed, 1)).await {
+            Err(heliosphere::Error::SignerError(e)) => assert!(e.contains("unauthorized"), "{}", e),
+            other => panic!("unexpected {:?}", other),
+        }
+
+        let (_, history) = AuditLog::open(&path).unwrap();
+        assert_eq!(history.len(), 3);
+        assert!(history[0].approved && !history[1].approved && !history[2].approved);
+        assert_eq!(history[0].spend.as_ref().unwrap().trx, 600);
+
+        // spend accounting survives restart
+        let mut policy = Policy::new(config).with_signer(Signer::address(&keypair()));
+        policy.replay(&history, now_ms());
+        assert!(policy.check(&transfer(allowed, 600), now_ms()).is_err());
+        std::fs::remove_file(path).unwrap();
+    }
+}
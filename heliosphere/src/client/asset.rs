@@ -0,0 +1,150 @@
+//! TRC10 assets
+use std::collections::BTreeMap;
+
+use heliosphere_core::{
+    contract::{
+        AssetIssueContract, ParticipateAssetIssueContract, TransferAssetContract,
+        UnfreezeAssetContract, UpdateAssetContract,
+    },
+    transaction::Transaction,
+    Address, Sun,
+};
+
+use super::{to_i64, AccountAssetsResponse, AssetIssueListResponse, RpcClient};
+
+impl RpcClient {
+    /** Create a TRC10 transfer transaction
+     ** from - Sender address
+     ** to - Receiver address
+     ** token_id - Asset id, e.g. `1002000`
+     ** amount - Amount in smallest units (see asset precision)
+     */
+    pub async fn transfer_asset(
+        &self,
+        from: &Address,
+        to: &Address,
+        token_id: &str,
+        amount: u64,
+    ) -> Result<Transaction, crate::Error> {
+        let contract = TransferAssetContract {
+            asset_name: token_id.as_bytes().to_vec(),
+            owner_address: *from,
+            to_address: *to,
+            amount: amount.try_into().map_err(|_| crate::Error::InvalidAmount)?,
+        };
+        self.create_transaction("/wallet/transferasset", contract)
+            .await
+    }
+
+    /// Issue TRC10 asset (one per account, costs 1024 TRX)
+    pub async fn create_asset_issue(
+        &self,
+        issue: &AssetIssueContract,
+    ) -> Result<Transaction, crate::Error> {
+        self.create_transaction("/wallet/createassetissue", issue.clone())
+            .await
+    }
+
+    /** Buy TRC10 asset during its ICO
+     ** owner - Buyer address
+     ** issuer - Asset issuer address
+     ** token_id - Asset id
+     ** amount - Amount of TRX to spend
+     */
+    pub async fn participate_asset_issue(
+        &self,
+        owner: &Address,
+        issuer: &Address,
+        token_id: &str,
+        amount: Sun,
+    ) -> Result<Transaction, crate::Error> {
+        let contract = ParticipateAssetIssueContract {
+            owner_address: *owner,
+            to_address: *issuer,
+            asset_name: token_id.as_bytes().to_vec(),
+            amount: to_i64(amount)?,
+        };
+        self.create_transaction("/wallet/participateassetissue", contract)
+            .await
+    }
+
+    /// Unfreeze issuer's frozen supply after its frozen days passed
+    pub async fn unfreeze_asset(&self, owner: &Address) -> Result<Transaction, crate::Error> {
+        let contract = UnfreezeAssetContract {
+            owner_address: *owner,
+        };
+        self.create_transaction("/wallet/unfreezeasset", contract)
+            .await
+    }
+
+    /// Update description, url and free bandwidth limits of issuer's asset
+    pub async fn update_asset(
+        &self,
+        update: &UpdateAssetContract,
+    ) -> Result<Transaction, crate::Error> {
+        self.create_transaction("/wallet/updateasset", update.clone())
+            .await
+    }
+
+    /// Query TRC10 asset by id, `None` if it doesn't exist
+    pub async fn get_asset_issue_by_id(
+        &self,
+        token_id: &str,
+    ) -> Result<Option<AssetIssueContract>, crate::Error> {
+        let resp: serde_json::Value = self
+            .api_post(
+                "/wallet/getassetissuebyid",
+                &serde_json::json!({ "value": token_id }),
+            )
+            .await?;
+        if resp.get("owner_address").is_none() {
+            return Ok(None);
+        }
+        serde_json::from_value(resp).map_err(|e| crate::Error::UnknownResponse(e.to_string()))
+    }
+
+    /// Query all TRC10 assets
+    pub async fn get_asset_issue_list(&self) -> Result<Vec<AssetIssueContract>, crate::Error> {
+        let resp: AssetIssueListResponse = self.api_get("/wallet/getassetissuelist").await?;
+        Ok(resp.asset_issue)
+    }
+
+    /** Query TRC10 assets
+     ** offset - Index of first asset
+     ** limit - Maximum number of assets to return
+     */
+    pub async fn get_paginated_asset_issue_list(
+        &self,
+        offset: u64,
+        limit: u64,
+    ) -> Result<Vec<AssetIssueContract>, crate::Error> {
+        let resp: AssetIssueListResponse = self
+            .api_post(
+                "/wallet/getpaginatedassetissuelist",
+                &serde_json::json!({ "offset": offset, "limit": limit }),
+            )
+            .await?;
+        Ok(resp.asset_issue)
+    }
+
+    /// Query TRC10 balances of account by asset id
+    pub async fn get_account_assets(
+        &self,
+        account: &Address,
+    ) -> Result<BTreeMap<String, u64>, crate::Error> {
+        let resp: AccountAssetsResponse = self
+            .api_post(
+                "/wallet/getaccount",
+                &serde_json::json!({ "address": account.as_hex() }),
+            )
+            .await?;
+        if resp.address.is_none() {
+            return Err(crate::Error::AccountNotFound);
+        }
+        Ok(resp
+            .asset_v2
+            .into_iter()
+            .map(|balance| (balance.key, balance.value))
+            .collect())
+    }
+}
@@ -1,19 +1,34 @@
-use std::{collections::BTreeMap, fmt::Debug, time::Duration};
+use std::{
+    collections::BTreeMap,
+    fmt::Debug,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 
 use heliosphere_core::{
     block::{Block, BlockBy, BlockHeader},
-    transaction::{Transaction, TransactionId},
-    Address,
+    contract::{
+        AccountCreateContract, AccountPermissionUpdateContract, AccountType, ContractKind,
+        TransferContract, TriggerSmartContract,
+    },
+    transaction::{
+        Contract, RawTxData, Transaction, TransactionBuilder, TransactionId, MAX_EXPIRATION,
+    },
+    util::method_selector,
+    Address, Sun,
 };
-use heliosphere_signer::signer::Signer;
+use heliosphere_signer::async_signer::AsyncSigner;
 use reqwest::{Client, IntoUrl, Url};
 use rpc_types::{RpcPayload, RpcResponse};
 use serde::{de::DeserializeOwned, Serialize};
 
-// Rpc response types
+mod asset;
+mod proposal;
+/// Rpc response types
 pub mod rpc_types;
+mod stake;
 /// Reponse types
 pub mod types;
+mod witness;
 pub use types::*;
 
 /// Method call params
@@ -28,11 +43,44 @@ pub struct MethodCall<'a> {
     pub parameter: &'a [u8],
 }
 
+/// Memo attached to node-built TRX transfers
+const TRX_TRANSFER_MEMO: [u8; 64] = [0x72; 64];
+
+/// Check that node did not attach unexpected memo or postpone expiration beyond java-tron maximum
+fn verify_memo_and_expiration(raw_data: &RawTxData, memo: &[u8]) -> Result<(), crate::Error> {
+    let data = raw_data.data.as_deref().unwrap_or_default();
+    if !data.eq_ignore_ascii_case(&hex::encode(memo)) {
+        return Err(crate::Error::TxVerificationFailed(format!(
+            "memo mismatch: expected {:?}, got {:?}",
+            hex::encode(memo),
+            data
+        )));
+    }
+    let timestamp = raw_data.timestamp.unwrap_or_default();
+    if raw_data.expiration > timestamp.saturating_add(MAX_EXPIRATION.as_millis() as u64) {
+        return Err(crate::Error::TxVerificationFailed(format!(
+            "expiration {} is more than 24 hours after timestamp {}",
+            raw_data.expiration, timestamp
+        )));
+    }
+    Ok(())
+}
+
+/// Convert SUN amount to protobuf int64
+fn to_i64(amount: Sun) -> Result<i64, crate::Error> {
+    amount
+        .as_sun()
+        .try_into()
+        .map_err(|_| crate::Error::InvalidAmount)
+}
+
 /// Builder struct for RpcClient
 pub struct RpcClientBuilder {
     client: Option<Client>,
     poll_interval: Duration,
     rpc_url: Url,
+    verify_transactions: bool,
+    build_locally: bool,
 }
 
 impl RpcClientBuilder {
@@ -45,6 +93,8 @@ impl RpcClientBuilder {
             client: None,
             poll_interval: Duration::from_secs(5),
             rpc_url: rpc_url.into_url().map_err(|_| crate::Error::InvalidUrl)?,
+            verify_transactions: true,
+            build_locally: false,
         })
     }
 
@@ -60,12 +110,30 @@ impl RpcClientBuilder {
         self
     }
 
+    /// Enable or disable verification of node-built transactions (enabled by default).
+    /// When enabled, `raw_data_hex` of every created transaction is decoded and checked
+    /// against `txID`, the requested contract parameters and memo, and 24 hour maximum expiration
+    /// before it is returned.
+    pub fn with_tx_verification(mut self, verify: bool) -> Self {
+        self.verify_transactions = verify;
+        self
+    }
+
+    /// Build TRX transfers and other typed contract transactions (e.g. Stake 2.0) locally,
+    /// referencing latest block, instead of calling node API (disabled by default)
+    pub fn with_local_tx_building(mut self, build_locally: bool) -> Self {
+        self.build_locally = build_locally;
+        self
+    }
+
     /// Build new RpcClient instance
     pub fn build(self) -> RpcClient {
         RpcClient {
             rpc_url: self.rpc_url,
             client: self.client.unwrap_or_default(),
             poll_interval: self.poll_interval,
+            verify_transactions: self.verify_transactions,
+            build_locally: self.build_locally,
         }
     }
 }
@@ -76,6 +144,8 @@ pub struct RpcClient {
     rpc_url: Url,
     client: Client,
     poll_interval: Duration,
+    verify_transactions: bool,
+    build_locally: bool,
 }
 
 impl RpcClient {
@@ -142,6 +212,45 @@ impl RpcClient {
             .await?)
     }
 
+    /// Check that node-built transaction hashes to its txID and contains exactly the expected contract,
+    /// memo (and fee limit if given), and expires within 24 hours. Does nothing if verification is disabled.
+    fn verify_transaction(
+        &self,
+        tx: &Transaction,
+        expected: &Contract,
+        memo: &[u8],
+        fee_limit: Option<Sun>,
+    ) -> Result<(), crate::Error> {
+        if !self.verify_transactions {
+            return Ok(());
+        }
+        let verification_failed = |e| crate::Error::TxVerificationFailed(format!("{:?}", e));
+        let raw_data = tx.decode_raw_data().map_err(verification_failed)?;
+        verify_memo_and_expiration(&raw_data, memo)?;
+        let [contract] = raw_data.contract.as_slice() else {
+            return Err(crate::Error::TxVerificationFailed(format!(
+                "expected 1 contract, got {}",
+                raw_data.contract.len()
+            )));
+        };
+        if contract.to_protobuf().map_err(verification_failed)?
+            != expected.to_protobuf().map_err(verification_failed)?
+        {
+            return Err(crate::Error::TxVerificationFailed(format!(
+                "contract mismatch: expected {:?}, got {:?}",
+                expected, contract
+            )));
+        }
+        if let Some(fee_limit) = fee_limit.filter(|x| x.as_sun() != raw_data.fee_limit) {
+            return Err(crate::Error::TxVerificationFailed(format!(
+                "fee limit mismatch: expected {}, got {}",
+                fee_limit,
+                Sun::new(raw_data.fee_limit)
+            )));
+        }
+        Ok(())
+    }
+
     /// Broadcast signed transaction
     pub async fn broadcast_transaction(
         &self,
@@ -160,6 +269,22 @@ impl RpcClient {
         }
     }
 
+    /** Sign transaction with local or remote signer and broadcast it
+     ** tx: Transaction to sign (signature is appended)
+     ** signer: Any `Signer` or `AsyncSigner`
+     */
+    pub async fn sign_and_broadcast(
+        &self,
+        tx: &mut Transaction,
+        signer: &impl AsyncSigner,
+    ) -> Result<TransactionId, crate::Error> {
+        signer
+            .sign_transaction(tx)
+            .await
+            .map_err(|e| crate::Error::SignerError(format!("{:?}", e)))?;
+        self.broadcast_transaction(tx).await
+    }
+
     /// Get latest block
     pub async fn get_latest_block(&self) -> Result<Block, crate::Error> {
         self.api_post("/wallet/getnowblock", &serde_json::json!({}))
@@ -234,24 +359,69 @@ impl RpcClient {
     /** Create a TRX transfer transaction
      ** from - Sender address
      ** to - Receiver address
-     ** amount - Raw amount of TRX to transfer in SUN (1 TRX = 1,000,000 SUN)
+     ** amount - Amount of TRX to transfer
      */
     pub async fn trx_transfer(
         &self,
         from: &Address,
         to: &Address,
-        amount: u64,
+        amount: Sun,
     ) -> Result<Transaction, crate::Error> {
-        self.api_post(
-            "/wallet/createtransaction",
-            &serde_json::json!({
-                "owner_address": from.as_hex(),
-                "to_address": to.as_hex(),
-                "amount": amount,
-                "extra_data": hex::encode([0x72; 64]),
-            }),
-        )
-        .await
+        let contract = TransferContract {
+            owner_address: *from,
+            to_address: *to,
+            amount: to_i64(amount)?,
+        };
+        if self.build_locally {
+            return self.build_transaction(contract).await;
+        }
+        let tx = self
+            .api_post(
+                "/wallet/createtransaction",
+                &serde_json::json!({
+                    "owner_address": from.as_hex(),
+                    "to_address": to.as_hex(),
+                    "amount": amount,
+                    "extra_data": hex::encode(TRX_TRANSFER_MEMO),
+                }),
+            )
+            .await?;
+        self.verify_transaction(&tx, &Contract::new(contract), &TRX_TRANSFER_MEMO, None)?;
+        Ok(tx)
+    }
+
+    /// Create transaction for typed contract with node API `method` and verify it,
+    /// or build it locally (see [`RpcClientBuilder::with_local_tx_building`])
+    async fn create_transaction<C>(
+        &self,
+        method: &str,
+        contract: C,
+    ) -> Result<Transaction, crate::Error>
+    where
+        C: Into<ContractKind> + Serialize,
+    {
+        if self.build_locally {
+            return self.build_transaction(contract).await;
+        }
+        let tx = self.api_post(method, &contract).await?;
+        self.verify_transaction(&tx, &Contract::new(contract), &[], None)?;
+        Ok(tx)
+    }
+
+    /// Build transaction locally from typed contract, referencing the latest block
+    pub async fn build_transaction(
+        &self,
+        contract: impl Into<ContractKind>,
+    ) -> Result<Transaction, crate::Error> {
+        let block = self.get_latest_block().await?;
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as u64;
+        Ok(TransactionBuilder::new(contract)
+            .with_ref_block(&block)
+            .with_timestamp(now)
+            .build()?)
     }
 
     /** Create an account
@@ -263,26 +433,53 @@ impl RpcClient {
         payer: &Address,
         account: &Address,
     ) -> Result<Transaction, crate::Error> {
-        self.api_post(
-            "/wallet/createaccount",
-            &serde_json::json!({
-                "owner_address": payer.as_hex(),
-                "account_address": account.as_hex(),
-            }),
-        )
-        .await
+        let tx = self
+            .api_post(
+                "/wallet/createaccount",
+                &serde_json::json!({
+                    "owner_address": payer.as_hex(),
+                    "account_address": account.as_hex(),
+                }),
+            )
+            .await?;
+        let expected = Contract::new(AccountCreateContract {
+            owner_address: *payer,
+            account_address: *account,
+            r#type: AccountType::Normal,
+        });
+        self.verify_transaction(&tx, &expected, &[], None)?;
+        Ok(tx)
     }
 
     /** Call a smart contract method
      ** method_call: Call parameters
-     ** value - Amount of TRX in SUN to send along with method call
-     ** fee_limit - Maximum TRX consumption, measured in SUN (1 TRX = 1,000,000 SUN)
+     ** value - Amount of TRX to send along with method call
+     ** fee_limit - Maximum TRX consumption (estimated if not set)
      */
     pub async fn trigger_contract(
         &self,
         method_call: &MethodCall<'_>,
-        value: u64,
-        fee_limit: Option<u64>,
+        value: Sun,
+        fee_limit: Option<Sun>,
+    ) -> Result<Transaction, crate::Error> {
+        self.trigger_contract_with_token(method_call, value, 0, 0, fee_limit)
+            .await
+    }
+
+    /** Call a smart contract method sending TRC10 tokens along
+     ** method_call: Call parameters
+     ** value - Amount of TRX to send along with method call
+     ** token_id - TRC10 asset id
+     ** token_value - Amount of TRC10 tokens to send along with method call
+     ** fee_limit - Maximum TRX consumption (estimated if not set)
+     */
+    pub async fn trigger_contract_with_token(
+        &self,
+        method_call: &MethodCall<'_>,
+        value: Sun,
+        token_id: u64,
+        token_value: u64,
+        fee_limit: Option<Sun>,
     ) -> Result<Transaction, crate::Error> {
         let fee_limit = match fee_limit {
             Some(fee_limit) => fee_limit,
@@ -297,10 +494,29 @@ impl RpcClient {
                     "function_selector": method_call.selector,
                     "parameter": hex::encode(method_call.parameter),
                     "fee_limit": fee_limit,
-                    "call_value": value
+                    "call_value": value,
+                    "call_token_value": token_value,
+                    "token_id": token_id
                 }),
             )
             .await?;
+        let expected = Contract::new(TriggerSmartContract {
+            owner_address: *method_call.caller,
+            contract_address: *method_call.contract,
+            call_value: to_i64(value)?,
+            data: [
+                &method_selector(method_call.selector)[..],
+                method_call.parameter,
+            ]
+            .concat(),
+            call_token_value: token_value
+                .try_into()
+                .map_err(|_| crate::Error::InvalidAmount)?,
+            token_id: token_id
+                .try_into()
+                .map_err(|_| crate::Error::InvalidAmount)?,
+        });
+        self.verify_transaction(&resp.transaction, &expected, &[], Some(fee_limit))?;
         Ok(resp.transaction)
     }
 
@@ -346,46 +562,46 @@ impl RpcClient {
         abi: &str,
         bytecode: &[u8],
         name: &str,
-        deployer: &impl Signer,
+        deployer: &impl AsyncSigner,
     ) -> Result<Address, crate::Error> {
-        let mut tx = self
+        let deployer_address = deployer
+            .address()
+            .await
+            .map_err(|e| crate::Error::SignerError(format!("{:?}", e)))?;
+        let mut tx: Transaction = self
             .api_post(
                 "/wallet/deploycontract",
                 &serde_json::json!({
                     "abi": abi,
                     "bytecode": hex::encode(bytecode),
                     "name": name,
-                    "owner_address": deployer.address(),
+                    "owner_address": deployer_address,
                     "visible": true
                 }),
             )
             .await?;
-        deployer
-            .sign_transaction(&mut tx)
-            .map_err(|e| crate::Error::SignerError(format!("{:?}", e)))?;
-        let txid = self.broadcast_transaction(&tx).await?;
-        let info = self.await_confirmation(txid).await?;
-        let contract = info
-            .transaction
-            .raw_data
-            .contract
-            .first()
-            .ok_or(crate::Error::ContractNotFound)?;
-        let new_address = contract
-            .parameter
-            .get("value")
-            .ok_or_else(|| crate::Error::UnknownResponse("no value field".to_owned()))?
-            .get("new_contract")
-            .ok_or_else(|| crate::Error::UnknownResponse("no new_contract field".to_owned()))?
-            .get("contract_address")
-            .ok_or_else(|| crate::Error::UnknownResponse("no contract_address field".to_owned()))?
-            .as_str()
-            .ok_or_else(|| {
-                crate::Error::UnknownResponse("returned contract_address is not string".to_owned())
-            })?;
-        new_address
-            .parse()
-            .map_err(|_| crate::Error::UnknownResponse(format!("Invalid address: {}", new_address)))
+        if self.verify_transactions {
+            let raw_data = tx
+                .decode_raw_data()
+                .map_err(|e| crate::Error::TxVerificationFailed(format!("{:?}", e)))?;
+            verify_memo_and_expiration(&raw_data, &[])?;
+            match raw_data.contract.as_slice() {
+                [Contract {
+                    kind: ContractKind::CreateSmartContract(c),
+                    ..
+                }] if c.owner_address == deployer_address
+                    && c.new_contract.bytecode == bytecode => {}
+                contracts => {
+                    return Err(crate::Error::TxVerificationFailed(format!(
+                        "unexpected contracts {:?}",
+                        contracts
+                    )))
+                }
+            }
+        }
+        let txid = self.sign_and_broadcast(&mut tx, deployer).await?;
+        self.await_confirmation(txid).await?;
+        Ok(Address::contract_address(&txid, &deployer_address))
     }
 
     /** Estimate energy cost of given smart contract call
@@ -402,13 +618,15 @@ impl RpcClient {
     pub async fn estimate_fee_limit(
         &self,
         method_call: &MethodCall<'_>,
-    ) -> Result<u64, crate::Error> {
+    ) -> Result<Sun, crate::Error> {
         let params = self.get_chain_parameters().await?;
         let energy_fee = *params
             .get("getEnergyFee")
             .ok_or_else(|| crate::Error::UnknownResponse("getEnergyFee not found".to_owned()))?
             as u64;
-        Ok(self.estimate_energy(method_call).await? * energy_fee)
+        Sun::new(self.estimate_energy(method_call).await?)
+            .checked_mul(energy_fee)
+            .ok_or(crate::Error::InvalidAmount)
     }
 
     /// Query the resource information of an account (bandwidth, energy, etc..)
@@ -424,7 +642,7 @@ impl RpcClient {
     }
 
     /// Query TRX account balance (including frozen)
-    pub async fn get_account_balance(&self, account: &Address) -> Result<u64, crate::Error> {
+    pub async fn get_account_balance(&self, account: &Address) -> Result<Sun, crate::Error> {
         let resp: AccountBalanceResponse = self
             .api_post(
                 "/wallet/getaccount",
@@ -434,6 +652,57 @@ impl RpcClient {
         resp.balance.ok_or(crate::Error::AccountNotFound)
     }
 
+    /// Query account permissions (owner, witness and active)
+    pub async fn get_account_permissions(
+        &self,
+        account: &Address,
+    ) -> Result<AccountPermissions, crate::Error> {
+        let resp: serde_json::Value = self
+            .api_post(
+                "/wallet/getaccount",
+                &serde_json::json!({ "address": account.as_hex() }),
+            )
+            .await?;
+        if resp.get("address").is_none() {
+            return Err(crate::Error::AccountNotFound);
+        }
+        serde_json::from_value(resp).map_err(|e| crate::Error::UnknownResponse(e.to_string()))
+    }
+
+    /** Update account permissions (multi-signature setup)
+     ** update - New owner, witness and active permissions of `update.owner_address`
+     */
+    pub async fn account_permission_update(
+        &self,
+        update: &AccountPermissionUpdateContract,
+    ) -> Result<Transaction, crate::Error> {
+        let tx = self
+            .api_post(
+                "/wallet/accountpermissionupdate",
+                &serde_json::to_value(update)
+                    .map_err(|e| crate::Error::UnknownResponse(e.to_string()))?,
+            )
+            .await?;
+        self.verify_transaction(&tx, &Contract::new(update.clone()), &[], None)?;
+        Ok(tx)
+    }
+
+    /// Query current signature weight of (partially) signed multi-signature transaction
+    pub async fn get_sign_weight(
+        &self,
+        tx: &Transaction,
+    ) -> Result<SignWeightResponse, crate::Error> {
+        self.api_post("/wallet/getsignweight", tx).await
+    }
+
+    /// Query addresses that signed transaction
+    pub async fn get_approved_list(
+        &self,
+        tx: &Transaction,
+    ) -> Result<ApprovedListResponse, crate::Error> {
+        self.api_post("/wallet/getapprovedlist", tx).await
+    }
+
     /// All parameters that the blockchain committee can set
     pub async fn get_chain_parameters(&self) -> Result<BTreeMap<String, i64>, crate::Error> {
         let resp: ChainParametersResponse = self.api_get("/wallet/getchainparameters").await?;
@@ -474,3 +743,51 @@ impl RpcClient {
             .await
     }
 }
+
+#[cfg(test)]
+mod test {
+    use heliosphere_core::block::BlockId;
+
+    use super::*;
+
+    #[test]
+    fn test_verify_transaction() {
+        let client = RpcClient::new("http://127.0.0.1:8090").unwrap();
+        let address: Address = "TNPeeaaFB7K9cmo4uQpcU32zGK8G1NYqeL".parse().unwrap();
+        let contract = Contract::new(TransferContract {
+            owner_address: address,
+            to_address: address,
+            amount: 1,
+        });
+        let build = |memo: &[u8], expiration| {
+            let builder = TransactionBuilder::new(contract.kind.clone())
+                .with_ref_block_id(BlockId([0x01; 32]))
+                .with_timestamp(1_000)
+                .with_expiration(expiration);
+            match memo {
+                [] => builder,
+                memo => builder.with_memo(memo),
+            }
+            .build()
+            .unwrap()
+        };
+
+        let tx = build(&TRX_TRANSFER_MEMO, MAX_EXPIRATION);
+        assert!(client
+            .verify_transaction(&tx, &contract, &TRX_TRANSFER_MEMO, None)
+            .is_ok());
+        assert!(client
+            .verify_transaction(&tx, &contract, &[], None)
+            .is_err());
+        let tx = build(b"", Duration::from_secs(60));
+        assert!(client.verify_transaction(&tx, &contract, &[], None).is_ok());
+        assert!(client
+            .verify_transaction(&tx, &contract, b"memo", None)
+            .is_err());
+        let tx = build(b"", MAX_EXPIRATION + Duration::from_millis(1));
+        assert!(matches!(
+            client.verify_transaction(&tx, &contract, &[], None),
+            Err(crate::Error::TxVerificationFailed(e)) if e.contains("expiration")
+        ));
+    }
+}
@@ -0,0 +1,119 @@
+//! Committee proposals
+use std::collections::BTreeMap;
+
+use heliosphere_core::{
+    contract::{ProposalApproveContract, ProposalCreateContract, ProposalDeleteContract},
+    proposal::parameter_id,
+    transaction::Transaction,
+    Address,
+};
+
+use super::{Proposal, ProposalListResponse, RpcClient};
+
+/// Proposal id as protobuf int64
+fn to_proposal_id(id: u64) -> Result<i64, crate::Error> {
+    id.try_into().map_err(|_| crate::Error::InvalidIndex)
+}
+
+impl RpcClient {
+    /** Create committee proposal
+     ** owner - Super representative address
+     ** parameters - New chain parameter values keyed by `get_chain_parameters` names (or numeric ids)
+     */
+    pub async fn proposal_create(
+        &self,
+        owner: &Address,
+        parameters: &BTreeMap<String, i64>,
+    ) -> Result<Transaction, crate::Error> {
+        let parameters = parameters
+            .iter()
+            .map(|(name, value)| {
+                let id = parameter_id(name)
+                    .or_else(|| name.parse().ok())
+                    .ok_or_else(|| crate::Error::UnknownChainParameter(name.clone()))?;
+                Ok((id, *value))
+            })
+            .collect::<Result<_, crate::Error>>()?;
+        let contract = ProposalCreateContract {
+            owner_address: *owner,
+            parameters,
+        };
+        self.create_transaction("/wallet/proposalcreate", contract)
+            .await
+    }
+
+    /** Approve proposal or withdraw approval
+     ** owner - Super representative address
+     ** proposal_id - Proposal id
+     ** approve - Add (true) or remove (false) approval
+     */
+    pub async fn proposal_approve(
+        &self,
+        owner: &Address,
+        proposal_id: u64,
+        approve: bool,
+    ) -> Result<Transaction, crate::Error> {
+        let contract = ProposalApproveContract {
+            owner_address: *owner,
+            proposal_id: to_proposal_id(proposal_id)?,
+            is_add_approval: approve,
+        };
+        self.create_transaction("/wallet/proposalapprove", contract)
+            .await
+    }
+
+    /// Delete pending proposal (proposer only)
+    pub async fn proposal_delete(
+        &self,
+        owner: &Address,
+        proposal_id: u64,
+    ) -> Result<Transaction, crate::Error> {
+        let contract = ProposalDeleteContract {
+            owner_address: *owner,
+            proposal_id: to_proposal_id(proposal_id)?,
+        };
+        self.create_transaction("/wallet/proposaldelete", contract)
+            .await
+    }
+
+    /// Query all proposals
+    pub async fn list_proposals(&self) -> Result<Vec<Proposal>, crate::Error> {
+        let resp: ProposalListResponse = self.api_get("/wallet/listproposals").await?;
+        Ok(resp.proposals)
+    }
+
+    /// Query proposal by id, `None` if it doesn't exist
+    pub async fn get_proposal_by_id(
+        &self,
+        proposal_id: u64,
+    ) -> Result<Option<Proposal>, crate::Error> {
+        let resp: serde_json::Value = self
+            .api_post(
+                "/wallet/getproposalbyid",
+                &serde_json::json!({ "id": proposal_id }),
+            )
+            .await?;
+        if resp.get("proposal_id").is_none() {
+            return Ok(None);
+        }
+        serde_json::from_value(resp).map_err(|e| crate::Error::UnknownResponse(e.to_string()))
+    }
+
+    /** Query proposals, newest first
+     ** offset - Index of first proposal
+     ** limit - Maximum number of proposals to return
+     */
+    pub async fn get_paginated_proposal_list(
+        &self,
+        offset: u64,
+        limit: u64,
+    ) -> Result<Vec<Proposal>, crate::Error> {
+        let resp: ProposalListResponse = self
+            .api_post(
+                "/wallet/getpaginatedproposallist",
+                &serde_json::json!({ "offset": offset, "limit": limit }),
+            )
+            .await?;
+        Ok(resp.proposals)
+    }
+}
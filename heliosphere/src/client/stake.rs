@@ -0,0 +1,214 @@
+//! Stake 2.0 resource management
+use heliosphere_core::{
+    contract::{
+        CancelAllUnfreezeV2Contract, DelegateResourceContract, FreezeBalanceV2Contract,
+        UnDelegateResourceContract, UnfreezeBalanceV2Contract, WithdrawExpireUnfreezeContract,
+    },
+    transaction::Transaction,
+    Address, Sun,
+};
+
+use super::{
+    to_i64, AvailableUnfreezeCountResponse, CanDelegatedMaxSizeResponse,
+    CanWithdrawUnfreezeAmountResponse, DelegatedResourceAccountIndex, DelegatedResourceResponse,
+    DelegatedResourceV2, ResourceType, RpcClient,
+};
+
+impl RpcClient {
+    /** Stake TRX to obtain resource (and TRON power)
+     ** owner - Account address
+     ** amount - Amount of TRX to stake
+     ** resource - Bandwidth or energy
+     */
+    pub async fn freeze_balance_v2(
+        &self,
+        owner: &Address,
+        amount: Sun,
+        resource: ResourceType,
+    ) -> Result<Transaction, crate::Error> {
+        let contract = FreezeBalanceV2Contract {
+            owner_address: *owner,
+            frozen_balance: to_i64(amount)?,
+            resource,
+        };
+        self.create_transaction("/wallet/freezebalancev2", contract)
+            .await
+    }
+
+    /** Unstake TRX, it can be withdrawn after waiting period (see `withdraw_expire_unfreeze`)
+     ** owner - Account address
+     ** amount - Amount of TRX to unstake
+     ** resource - Bandwidth or energy
+     */
+    pub async fn unfreeze_balance_v2(
+        &self,
+        owner: &Address,
+        amount: Sun,
+        resource: ResourceType,
+    ) -> Result<Transaction, crate::Error> {
+        let contract = UnfreezeBalanceV2Contract {
+            owner_address: *owner,
+            unfreeze_balance: to_i64(amount)?,
+            resource,
+        };
+        self.create_transaction("/wallet/unfreezebalancev2", contract)
+            .await
+    }
+
+    /** Delegate resource obtained by staking to another account
+     ** owner - Account address
+     ** receiver - Resource receiver
+     ** amount - Amount of staked TRX to delegate
+     ** resource - Bandwidth or energy
+     ** lock_period - Lock delegation for number of blocks (0 for default 3 days), `None` for no lock
+     */
+    pub async fn delegate_resource(
+        &self,
+        owner: &Address,
+        receiver: &Address,
+        amount: Sun,
+        resource: ResourceType,
+        lock_period: Option<u64>,
+    ) -> Result<Transaction, crate::Error> {
+        let contract = DelegateResourceContract {
+            owner_address: *owner,
+            resource,
+            balance: to_i64(amount)?,
+            receiver_address: *receiver,
+            lock: lock_period.is_some(),
+            lock_period: lock_period
+                .unwrap_or(0)
+                .try_into()
+                .map_err(|_| crate::Error::InvalidAmount)?,
+        };
+        self.create_transaction("/wallet/delegateresource", contract)
+            .await
+    }
+
+    /** Cancel resource delegation
+     ** owner - Account address
+     ** receiver - Resource receiver
+     ** amount - Amount of staked TRX to undelegate
+     ** resource - Bandwidth or energy
+     */
+    pub async fn undelegate_resource(
+        &self,
+        owner: &Address,
+        receiver: &Address,
+        amount: Sun,
+        resource: ResourceType,
+    ) -> Result<Transaction, crate::Error> {
+        let contract = UnDelegateResourceContract {
+            owner_address: *owner,
+            resource,
+            balance: to_i64(amount)?,
+            receiver_address: *receiver,
+        };
+        self.create_transaction("/wallet/undelegateresource", contract)
+            .await
+    }
+
+    /// Withdraw unstaked TRX which passed waiting period
+    pub async fn withdraw_expire_unfreeze(
+        &self,
+        owner: &Address,
+    ) -> Result<Transaction, crate::Error> {
+        let contract = WithdrawExpireUnfreezeContract {
+            owner_address: *owner,
+        };
+        self.create_transaction("/wallet/withdrawexpireunfreeze", contract)
+            .await
+    }
+
+    /// Cancel all pending unstakes: TRX is staked again, expired unstakes are withdrawn
+    pub async fn cancel_all_unfreeze_v2(
+        &self,
+        owner: &Address,
+    ) -> Result<Transaction, crate::Error> {
+        let contract = CancelAllUnfreezeV2Contract {
+            owner_address: *owner,
+        };
+        self.create_transaction("/wallet/cancelallunfreezev2", contract)
+            .await
+    }
+
+    /// Query resources delegated by `from` to `to`
+    pub async fn get_delegated_resource_v2(
+        &self,
+        from: &Address,
+        to: &Address,
+    ) -> Result<Vec<DelegatedResourceV2>, crate::Error> {
+        let resp: DelegatedResourceResponse = self
+            .api_post(
+                "/wallet/getdelegatedresourcev2",
+                &serde_json::json!({
+                    "fromAddress": from.as_hex(),
+                    "toAddress": to.as_hex(),
+                }),
+            )
+            .await?;
+        Ok(resp.delegated_resource)
+    }
+
+    /// Query accounts `account` delegated resources to and received resources from
+    pub async fn get_delegated_resource_account_index_v2(
+        &self,
+        account: &Address,
+    ) -> Result<DelegatedResourceAccountIndex, crate::Error> {
+        self.api_post(
+            "/wallet/getdelegatedresourceaccountindexv2",
+            &serde_json::json!({ "value": account.as_hex() }),
+        )
+        .await
+    }
+
+    /** Query amount of unstaked TRX which can be withdrawn
+     ** owner - Account address
+     ** timestamp - Time in milliseconds to check at (e.g. now)
+     */
+    pub async fn get_can_withdraw_unfreeze_amount(
+        &self,
+        owner: &Address,
+        timestamp: u64,
+    ) -> Result<Sun, crate::Error> {
+        let resp: CanWithdrawUnfreezeAmountResponse = self
+            .api_post(
+                "/wallet/getcanwithdrawunfreezeamount",
+                &serde_json::json!({
+                    "owner_address": owner.as_hex(),
+                    "timestamp": timestamp,
+                }),
+            )
+            .await?;
+        Ok(resp.amount)
+    }
+
+    /// Query maximum amount of staked TRX which can be delegated as `resource`
+    pub async fn get_can_delegated_max_size(
+        &self,
+        owner: &Address,
+        resource: ResourceType,
+    ) -> Result<Sun, crate::Error> {
+        let resp: CanDelegatedMaxSizeResponse = self
+            .api_post(
+                "/wallet/getcandelegatedmaxsize",
+                &serde_json::json!({
+                    "owner_address": owner.as_hex(),
+                    "type": resource as i32,
+                }),
+            )
+            .await?;
+        Ok(resp.max_size)
+    }
+
+    /// Query number of unstake operations left (at most 32 pending unstakes are allowed)
+    pub async fn get_available_unfreeze_count(&self, owner: &Address) -> Result<u64, crate::Error> {
+        let resp: AvailableUnfreezeCountResponse = self
+            .api_post(
+                "/wallet/getavailableunfreezecount",
+                &serde_json::json!({ "owner_address": owner.as_hex() }),
+            )
+            .await?;
+        Ok(resp.count)
+    }
+}
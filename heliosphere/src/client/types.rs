@@ -1,8 +1,9 @@
 use std::collections::BTreeMap;
 
 use heliosphere_core::{
+    contract::Permission,
     transaction::{Transaction, TransactionId},
-    Address,
+    Address, Sun,
 };
 use serde::{Deserialize, Serialize};
 
@@ -63,15 +64,7 @@ pub struct TriggerContractResponse {
     pub transaction: Transaction,
 }
 
-/// Resource type: Energy or Bandwidth
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
-#[serde(rename_all = "UPPERCASE")]
-pub enum ResourceType {
-    /// Bandwidth resource
-    Bandwidth,
-    /// Energy resource
-    Energy,
-}
+pub use heliosphere_core::contract::ResourceType;
 
 /// Account resources
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -151,7 +144,249 @@ pub struct ChainParametersResponse {
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub struct AccountBalanceResponse {
     /// TRX balance
-    pub balance: Option<u64>,
+    pub balance: Option<Sun>,
+}
+
+/// Stake 2.0 resources delegated from one account to another
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct DelegatedResourceV2 {
+    /// Delegating account
+    pub from: Address,
+    /// Receiving account
+    pub to: Address,
+    /// Staked TRX delegated as bandwidth
+    #[serde(default)]
+    pub frozen_balance_for_bandwidth: Sun,
+    /// Staked TRX delegated as energy
+    #[serde(default)]
+    pub frozen_balance_for_energy: Sun,
+    /// Bandwidth delegation lock expiration time (ms)
+    #[serde(default)]
+    pub expire_time_for_bandwidth: u64,
+    /// Energy delegation lock expiration time (ms)
+    #[serde(default)]
+    pub expire_time_for_energy: u64,
+}
+
+/// Delegated resources as returned by GetDelegatedResourceV2
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct DelegatedResourceResponse {
+    /// Delegations
+    #[serde(rename = "delegatedResource", default)]
+    pub delegated_resource: Vec<DelegatedResourceV2>,
+}
+
+/// Stake 2.0 delegation counterparties of an account
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct DelegatedResourceAccountIndex {
+    /// Account address
+    pub account: Option<Address>,
+    /// Accounts which delegated resources to this account
+    #[serde(rename = "fromAccounts", default)]
+    pub from_accounts: Vec<Address>,
+    /// Accounts this account delegated resources to
+    #[serde(rename = "toAccounts", default)]
+    pub to_accounts: Vec<Address>,
+}
+
+/// Response of GetCanWithdrawUnfreezeAmount
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct CanWithdrawUnfreezeAmountResponse {
+    /// Withdrawable TRX
+    #[serde(default)]
+    pub amount: Sun,
+}
+
+/// Response of GetCanDelegatedMaxSize
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct CanDelegatedMaxSizeResponse {
+    /// Maximum delegatable staked TRX
+    #[serde(default)]
+    pub max_size: Sun,
+}
+
+/// Response of GetAvailableUnfreezeCount
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct AvailableUnfreezeCountResponse {
+    /// Remaining unstake operations
+    #[serde(default)]
+    pub count: u64,
+}
+
+pub use heliosphere_core::contract::Vote;
+
+/// Super representative (or candidate)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct Witness {
+    /// Witness address
+    pub address: Address,
+    /// Number of votes received
+    #[serde(rename = "voteCount", default)]
+    pub vote_count: u64,
+    /// Website url
+    #[serde(default)]
+    pub url: String,
+    /// Number of produced blocks
+    #[serde(rename = "totalProduced", default)]
+    pub total_produced: u64,
+    /// Number of missed blocks
+    #[serde(rename = "totalMissed", default)]
+    pub total_missed: u64,
+    /// Number of the last produced block
+    #[serde(rename = "latestBlockNum", default)]
+    pub latest_block_num: u64,
+    /// Slot of the last produced block
+    #[serde(rename = "latestSlotNum", default)]
+    pub latest_slot_num: u64,
+    /// Whether witness is an active super representative (top 27)
+    #[serde(rename = "isJobs", default)]
+    pub is_jobs: bool,
+}
+
+/// Witness list as returned by ListWitnesses
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct WitnessListResponse {
+    /// Witnesses
+    #[serde(default)]
+    pub witnesses: Vec<Witness>,
+}
+
+/// Response of GetReward
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct RewardResponse {
+    /// Unclaimed reward
+    #[serde(default)]
+    pub reward: Sun,
+}
+
+/// Response of GetBrokerage
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct BrokerageResponse {
+    /// Brokerage percentage
+    #[serde(default)]
+    pub brokerage: u8,
+}
+
+/// Committee proposal state
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq, Hash)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ProposalState {
+    /// Waiting for approvals
+    #[default]
+    Pending,
+    /// Expired without enough approvals
+    Disapproved,
+    /// Approved, parameters take effect at next maintenance period
+    Approved,
+    /// Deleted by proposer
+    Canceled,
+}
+
+/// Committee proposal
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct Proposal {
+    /// Proposal id
+    pub proposal_id: u64,
+    /// Proposer (super representative) address
+    pub proposer_address: Address,
+    /// Proposed chain parameter values keyed by `get_chain_parameters` names
+    /// (numeric id for parameters unknown to this crate)
+    #[serde(with = "proposal_parameters", default)]
+    pub parameters: BTreeMap<String, i64>,
+    /// Voting deadline (ms)
+    pub expiration_time: u64,
+    /// Creation time (ms)
+    pub create_time: u64,
+    /// Super representatives who approved proposal
+    #[serde(default)]
+    pub approvals: Vec<Address>,
+    /// State
+    #[serde(default)]
+    pub state: ProposalState,
+}
+
+/// Ser/De proposal parameters `[{"key": id, "value": value}]` as map keyed by name
+mod proposal_parameters {
+    use std::collections::BTreeMap;
+
+    use heliosphere_core::proposal::{parameter_id, parameter_name};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Serialize, Deserialize)]
+    struct Parameter {
+        key: i64,
+        #[serde(default)]
+        value: i64,
+    }
+
+    pub fn serialize<S>(params: &BTreeMap<String, i64>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        params
+            .iter()
+            .map(|(name, value)| {
+                let key = parameter_id(name)
+                    .or_else(|| name.parse().ok())
+                    .ok_or_else(|| {
+                        serde::ser::Error::custom(format!("unknown parameter {}", name))
+                    })?;
+                Ok(Parameter { key, value: *value })
+            })
+            .collect::<Result<Vec<_>, _>>()?
+            .serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<BTreeMap<String, i64>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(Vec::<Parameter>::deserialize(deserializer)?
+            .into_iter()
+            .map(|p| {
+                let name = parameter_name(p.key).map_or_else(|| p.key.to_string(), str::to_owned);
+                (name, p.value)
+            })
+            .collect())
+    }
+}
+
+/// Proposal list as returned by ListProposals
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct ProposalListResponse {
+    /// Proposals
+    #[serde(default)]
+    pub proposals: Vec<Proposal>,
+}
+
+pub use heliosphere_core::contract::{AssetIssueContract, FrozenSupply, UpdateAssetContract};
+
+/// TRC10 asset list as returned by GetAssetIssueList
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AssetIssueListResponse {
+    /// Assets
+    #[serde(rename = "assetIssue", default)]
+    pub asset_issue: Vec<AssetIssueContract>,
+}
+
+/// TRC10 balance entry (key, value)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct AssetBalance {
+    /// Asset id
+    pub key: String,
+    /// Balance
+    #[serde(default)]
+    pub value: u64,
+}
+
+/// Account TRC10 balances (as returned by /wallet/getaccount)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
+pub struct AccountAssetsResponse {
+    /// Account address
+    pub address: Option<Address>,
+    /// Balances by asset id
+    #[serde(rename = "assetV2", default)]
+    pub asset_v2: Vec<AssetBalance>,
 }
 
 /// Transaction execution result
@@ -163,7 +398,7 @@ pub struct ResourceReceipt {
     /// The amount of energy consumed in the caller's account
     pub energy_usage: Option<u64>,
     /// The amount of TRX burned to pay for energy
-    pub energy_fee: Option<u64>,
+    pub energy_fee: Option<Sun>,
     /// The amount of energy consumed in the contract deployer's account
     pub origin_energy_usage: Option<u64>,
     /// The total amount of energy consumed by the transaction
@@ -171,7 +406,7 @@ pub struct ResourceReceipt {
     /// The amount of bandwidth consumed
     pub net_usage: Option<u64>,
     /// The amount of TRX burned to pay for the bandwidth
-    pub net_fee: Option<u64>,
+    pub net_fee: Option<Sun>,
     /// Transaction execution result
     pub result: Option<TransactionResult>,
     /// The amount of extra energy that needs to be paid for calling a few popular contracts
@@ -181,16 +416,13 @@ pub struct ResourceReceipt {
 /// The log of events triggered during the smart contract call
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub struct Log {
-    /** Contract address. In order to be compatible with EVM, the address in TVM is a hex
-     format address without the prefix 0x41, so if you want to parse the address in the log,
-     you need to add 41 to the beginning of the log address,
-     and then convert it to Base58 format.
-    */
-    address: Address,
+    /// Contract address (TVM logs carry hex address without 0x41 prefix)
+    #[serde(with = "heliosphere_core::util::as_log_address")]
+    pub address: Address,
     /// The topic of the event, including the event itself and parameters marked as indexed.
-    topics: Vec<String>,
+    pub topics: Vec<String>,
     /// Non-indexed parameters of events.
-    data: String,
+    pub data: String,
 }
 
 /// Call value info
@@ -198,10 +430,10 @@ pub struct Log {
 pub struct CallValueInfo {
     /// The amount of TRX/TRC10 tokens transferred
     #[serde(rename = "callValue")]
-    call_value: Option<u64>,
+    pub call_value: Option<u64>,
     /// TRC10 name or id of the transfer; when transferring TRX, this field is empty
     #[serde(rename = "tokenId")]
-    token_id: Option<String>,
+    pub token_id: Option<String>,
 }
 
 /// Internal transaction
@@ -233,7 +465,7 @@ pub struct TransactionInfo {
      including TRX burned for bandwidth/energy, memo fee,
      account activation fee, multi-signature fee and other fees
     */
-    pub fee: Option<u64>,
+    pub fee: Option<Sun>,
     /// The block number
     #[serde(rename = "blockNumber")]
     pub block_number: u64,
@@ -263,12 +495,12 @@ pub struct TransactionInfo {
         The number of rewards withdrawn to the account is returned through this field
         and the unit is sun
     */
-    pub withdraw_amount: Option<u64>,
+    pub withdraw_amount: Option<Sun>,
     /** In the Stake1.0 stage, for unstaking transactions,
         this field returns the amount of unstaked TRX,
         the unit is sun
     */
-    pub unfreeze_amount: Option<u64>,
+    pub unfreeze_amount: Option<Sun>,
     /// Internal transaction
     pub internal_transactions: Option<Vec<InternalTransaction>>,
     /** In the Stake2.0 stage, for unstaking transaction and withdrawing unfrozen balance transaction,
@@ -276,12 +508,217 @@ pub struct TransactionInfo {
         this field returns the amount of unfrozen TRX withdrawn to the account in this transaction,
         the unit is sun
     */
-    pub withdraw_expire_amount: Option<u64>,
+    pub withdraw_expire_amount: Option<Sun>,
     /** The amount of TRX re-staked to obtain various types of resources,
         in sun, that is, the amount of unstaked principal that has been canceled,
         the key is: "BANDWIDTH" or "ENERGY" or "TRON_POWER"
     */
     // TODO: Add mapping to enum
     #[serde(rename = "cancel_unfreezeV2_amount")]
-    pub cancel_unfreeze_v2_amount: Option<BTreeMap<String, u64>>,
+    pub cancel_unfreeze_v2_amount: Option<BTreeMap<String, Sun>>,
+}
+
+/// Account permissions (as returned by /wallet/getaccount)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct AccountPermissions {
+    /// Owner permission
+    #[serde(rename = "owner_permission")]
+    pub owner: Option<Permission>,
+    /// Witness permission (super representatives only)
+    #[serde(rename = "witness_permission")]
+    pub witness: Option<Permission>,
+    /// Active permissions
+    #[serde(rename = "active_permission", default)]
+    pub actives: Vec<Permission>,
+}
+
+impl AccountPermissions {
+    /// Find permission by id
+    pub fn get(&self, permission_id: i32) -> Option<&Permission> {
+        self.owner
+            .iter()
+            .chain(self.witness.iter())
+            .chain(self.actives.iter())
+            .find(|p| p.id == permission_id)
+    }
+}
+
+/// Signature weight check result (as returned by /wallet/getsignweight)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SignWeightResponse {
+    /// Permission the transaction is signed with
+    pub permission: Option<Permission>,
+    /// Addresses of permission keys that signed
+    #[serde(default)]
+    pub approved_list: Vec<Address>,
+    /// Sum of weights of keys that signed
+    #[serde(default)]
+    pub current_weight: i64,
+    /// Check result
+    pub result: SignatureCheckResult,
+}
+
+impl SignWeightResponse {
+    /// Whether collected signatures reach permission threshold
+    pub fn is_enough(&self) -> bool {
+        self.result.code.as_deref() == Some("ENOUGH_PERMISSION")
+    }
+}
+
+/// Approved list (as returned by /wallet/getapprovedlist)
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ApprovedListResponse {
+    /// Addresses that signed the transaction
+    #[serde(default)]
+    pub approved_list: Vec<Address>,
+    /// Check result
+    pub result: SignatureCheckResult,
+}
+
+/// Signature check result
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SignatureCheckResult {
+    /// Result code, e.g. `ENOUGH_PERMISSION`, `NOT_ENOUGH_PERMISSION`, `SIGNATURE_FORMAT_ERROR`
+    #[serde(default)]
+    pub code: Option<String>,
+    /// Error message
+    #[serde(default)]
+    pub message: Option<String>,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_log_address() {
+        let log: Log = serde_json::from_str(
+            r#"{
+                "address": "a614f803b6fd780986a42c78ec9c7f77e6ded13c",
+                "topics": ["ddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef"],
+                "data": "00000000000000000000000000000000000000000000000000000000000f4240"
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            log.address.as_base58(),
+            "TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t"
+        );
+        let json = serde_json::to_value(&log).unwrap();
+        assert_eq!(json["address"], "a614f803b6fd780986a42c78ec9c7f77e6ded13c");
+    }
+
+    #[test]
+    fn test_stake_responses() {
+        let resp: DelegatedResourceResponse = serde_json::from_str(
+            r#"{"delegatedResource": [{
+                "from": "41a614f803b6fd780986a42c78ec9c7f77e6ded13c",
+                "to": "418840e6c55b9ada326d211d818c34a994aeced808",
+                "frozen_balance_for_energy": 100000000,
+                "expire_time_for_energy": 1690000000000
+            }]}"#,
+        )
+        .unwrap();
+        let delegation = &resp.delegated_resource[0];
+        assert_eq!(delegation.frozen_balance_for_energy, Sun::new(100_000_000));
+        assert_eq!(delegation.frozen_balance_for_bandwidth, Sun::ZERO);
+        assert_eq!(
+            delegation.to.as_base58(),
+            "TNPeeaaFB7K9cmo4uQpcU32zGK8G1NYqeL"
+        );
+
+        // zero values are omitted by node
+        let index: DelegatedResourceAccountIndex = serde_json::from_str("{}").unwrap();
+        assert!(index.account.is_none() && index.to_accounts.is_empty());
+        let resp: CanDelegatedMaxSizeResponse = serde_json::from_str("{}").unwrap();
+        assert_eq!(resp.max_size, Sun::ZERO);
+    }
+
+    #[test]
+    fn test_witness_list() {
+        let resp: WitnessListResponse = serde_json::from_str(
+            r#"{"witnesses": [{
+                "address": "418840e6c55b9ada326d211d818c34a994aeced808",
+                "voteCount": 2500000000,
+                "url": "https://example.com",
+                "totalProduced": 1000,
+                "totalMissed": 2,
+                "latestBlockNum": 50000000,
+                "latestSlotNum": 560000000,
+                "isJobs": true
+            }, {
+                "address": "41a614f803b6fd780986a42c78ec9c7f77e6ded13c",
+                "url": "https://candidate.example.com"
+            }]}"#,
+        )
+        .unwrap();
+        assert_eq!(resp.witnesses[0].vote_count, 2_500_000_000);
+        assert!(resp.witnesses[0].is_jobs);
+        assert_eq!(resp.witnesses[1].vote_count, 0);
+        assert!(!resp.witnesses[1].is_jobs);
+    }
+
+    #[test]
+    fn test_proposal() {
+        let resp: ProposalListResponse = serde_json::from_str(
+            r#"{"proposals": [{
+                "proposal_id": 90,
+                "proposer_address": "418840e6c55b9ada326d211d818c34a994aeced808",
+                "parameters": [{"key": 11, "value": 420}, {"key": 1000, "value": 1}],
+                "expiration_time": 1700000000000,
+                "create_time": 1699000000000,
+                "approvals": ["41a614f803b6fd780986a42c78ec9c7f77e6ded13c"],
+                "state": "APPROVED"
+            }]}"#,
+        )
+        .unwrap();
+        let proposal = &resp.proposals[0];
+        assert_eq!(proposal.parameters["getEnergyFee"], 420);
+        assert_eq!(proposal.parameters["1000"], 1);
+        assert_eq!(proposal.state, ProposalState::Approved);
+        assert_eq!(proposal.approvals.len(), 1);
+
+        let json = serde_json::to_value(proposal).unwrap();
+        assert!(json["parameters"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|p| p["key"] == 11 && p["value"] == 420));
+        assert_eq!(serde_json::from_value::<Proposal>(json).unwrap(), *proposal);
+    }
+
+    #[test]
+    fn test_assets() {
+        let resp: AssetIssueListResponse = serde_json::from_str(
+            r#"{"assetIssue": [{
+                "owner_address": "418840e6c55b9ada326d211d818c34a994aeced808",
+                "name": "54657374",
+                "abbr": "545354",
+                "total_supply": 1000000,
+                "trx_num": 1,
+                "precision": 6,
+                "num": 1,
+                "start_time": 1600000000000,
+                "end_time": 1700000000000,
+                "url": "6578616d706c652e636f6d",
+                "id": "1002000"
+            }]}"#,
+        )
+        .unwrap();
+        let asset = &resp.asset_issue[0];
+        assert_eq!(asset.id, "1002000");
+        assert_eq!(asset.name, b"Test");
+        assert_eq!(asset.precision, 6);
+
+        let account: AccountAssetsResponse = serde_json::from_str(
+            r#"{
+                "address": "418840e6c55b9ada326d211d818c34a994aeced808",
+                "balance": 1000,
+                "assetV2": [{"key": "1002000", "value": 500}, {"key": "1000001"}]
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(account.asset_v2[0].value, 500);
+        assert_eq!(account.asset_v2[1].value, 0);
+    }
 }
@@ -0,0 +1,120 @@
+//! Voting, super representatives and rewards
+use heliosphere_core::{
+    contract::{
+        UpdateBrokerageContract, VoteWitnessContract, WithdrawBalanceContract,
+        WitnessCreateContract,
+    },
+    transaction::Transaction,
+    Address, Sun,
+};
+
+use super::{BrokerageResponse, RewardResponse, RpcClient, Vote, Witness, WitnessListResponse};
+
+impl RpcClient {
+    /** Vote for super representatives, replaces previous votes of the account
+     ** owner - Voter address
+     ** votes - Witness addresses and vote counts (1 vote per staked TRX)
+     */
+    pub async fn vote_witness_account(
+        &self,
+        owner: &Address,
+        votes: &[Vote],
+    ) -> Result<Transaction, crate::Error> {
+        let contract = VoteWitnessContract {
+            owner_address: *owner,
+            votes: votes.to_vec(),
+            support: false,
+        };
+        self.create_transaction("/wallet/votewitnessaccount", contract)
+            .await
+    }
+
+    /// Query all witnesses with vote counts as of last maintenance period
+    pub async fn list_witnesses(&self) -> Result<Vec<Witness>, crate::Error> {
+        let resp: WitnessListResponse = self.api_get("/wallet/listwitnesses").await?;
+        Ok(resp.witnesses)
+    }
+
+    /** Query witnesses with real time vote counts, sorted by votes
+     ** offset - Index of first witness
+     ** limit - Maximum number of witnesses to return
+     */
+    pub async fn get_paginated_now_witness_list(
+        &self,
+        offset: u64,
+        limit: u64,
+    ) -> Result<Vec<Witness>, crate::Error> {
+        let resp: WitnessListResponse = self
+            .api_post(
+                "/wallet/getpaginatednowwitnesslist",
+                &serde_json::json!({ "offset": offset, "limit": limit }),
+            )
+            .await?;
+        Ok(resp.witnesses)
+    }
+
+    /// Query unclaimed voting reward of account
+    pub async fn get_reward(&self, account: &Address) -> Result<Sun, crate::Error> {
+        let resp: RewardResponse = self
+            .api_post(
+                "/wallet/getReward",
+                &serde_json::json!({ "address": account.as_hex() }),
+            )
+            .await?;
+        Ok(resp.reward)
+    }
+
+    /// Claim voting reward (and block producing reward of witnesses), allowed once per 24 hours
+    pub async fn withdraw_balance(&self, owner: &Address) -> Result<Transaction, crate::Error> {
+        let contract = WithdrawBalanceContract {
+            owner_address: *owner,
+        };
+        self.create_transaction("/wallet/withdrawbalance", contract)
+            .await
+    }
+
+    /// Query witness brokerage: percentage of voter rewards kept by witness
+    pub async fn get_brokerage(&self, witness: &Address) -> Result<u8, crate::Error> {
+        let resp: BrokerageResponse = self
+            .api_post(
+                "/wallet/getBrokerage",
+                &serde_json::json!({ "address": witness.as_hex() }),
+            )
+            .await?;
+        Ok(resp.brokerage)
+    }
+
+    /** Update witness brokerage
+     ** owner - Witness address
+     ** brokerage - Percentage of voter rewards kept by witness (0-100)
+     */
+    pub async fn update_brokerage(
+        &self,
+        owner: &Address,
+        brokerage: u8,
+    ) -> Result<Transaction, crate::Error> {
+        let contract = UpdateBrokerageContract {
+            owner_address: *owner,
+            brokerage: brokerage.into(),
+        };
+        self.create_transaction("/wallet/updateBrokerage", contract)
+            .await
+    }
+
+    /** Apply to become super representative candidate (burns 9999 TRX)
+     ** owner - Account address
+     ** url - Candidate website
+     */
+    pub async fn create_witness(
+        &self,
+        owner: &Address,
+        url: &str,
+    ) -> Result<Transaction, crate::Error> {
+        let contract = WitnessCreateContract {
+            owner_address: *owner,
+            url: url.as_bytes().to_vec(),
+        };
+        self.create_transaction("/wallet/createwitness", contract)
+            .await
+    }
+}
@@ -4,6 +4,7 @@ pub enum Error {
     /// Invalid URL
     #[error("invalid url")]
     InvalidUrl,
+    /// Failed to build request url
     #[error("fail url parse")]
     UrlBuildFail(#[from] url::ParseError),
     /// RpcError wrapping reqwest::Error
@@ -24,16 +25,37 @@ pub enum Error {
     /// Returned when RPC returns invalid or unknown response
     #[error("rpc returned unknown response type")]
     UnknownResponse(String),
+    /// Amount does not fit into int64
+    #[error("amount out of range")]
+    InvalidAmount,
+    /// Token amount decimals differ from token decimals (token, amount)
+    #[error("token has {0} decimals, amount has {1}")]
+    TokenDecimalsMismatch(u8, u8),
     /// Invalid index passed
     #[error("invalid index")]
     InvalidIndex,
     /// Contract does not exists
     #[error("contract not found")]
     ContractNotFound,
+    /// Chain parameter name is not known to this crate
+    #[error("unknown chain parameter {0}")]
+    UnknownChainParameter(String),
     /// Account does not exists
     #[error("account not found")]
     AccountNotFound,
+    /// Node-built transaction does not match the request (or its txID)
+    #[error("tx verification failed: {0}")]
+    TxVerificationFailed(String),
+    /// Core error (encoding, parsing, ..)
+    #[error("core error: {0}")]
+    CoreError(heliosphere_core::Error),
     /// Failed to sign transaction
     #[error("Failed to sign tx: {0}")]
     SignerError(String),
 }
+
+impl From<heliosphere_core::Error> for Error {
+    fn from(e: heliosphere_core::Error) -> Self {
+        Self::CoreError(e)
+    }
+}
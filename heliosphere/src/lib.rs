@@ -7,5 +7,8 @@ mod client;
 pub use client::*;
 mod error;
 pub use error::Error;
+pub mod remote_signer;
+pub mod trc20;
 pub use heliosphere_core as core;
 pub use heliosphere_signer as signer;
+pub use trc20::Trc20;
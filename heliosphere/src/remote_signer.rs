@@ -0,0 +1,299 @@
+//! Remote signer speaking a simple HTTP/JSON signing protocol
+//!
+//! - `GET  {url}/public_key` -> `{"public_key": "<hex SEC1 public key>"}`
+//! - `POST {url}/sign_transaction` with `{"transaction": <Transaction>}` -> `{"signature": "<hex r || s || v>"}`
+//! - `POST {url}/sign_prehash` with `{"prehash": "<hex 32 bytes>"}` -> `{"signature": "<hex r || s || v>"}`
+//!
+//! Errors are reported with non-2xx status and `{"error": "<message>"}` body.
+//! Every returned signature is checked to recover to the signer public key.
+
+use std::sync::OnceLock;
+
+use heliosphere_core::transaction::{Transaction, TronSignature};
+use heliosphere_signer::{
+    async_signer::AsyncSigner,
+    k256::ecdsa::{RecoveryId, Signature, VerifyingKey},
+    signer::recover_public_key,
+};
+use reqwest::{Client, IntoUrl, Url};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+
+/// Public key response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PublicKeyResponse {
+    /// Hex SEC1 encoded (compressed or uncompressed) public key
+    pub public_key: String,
+}
+
+/// Transaction signing request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignTransactionRequest {
+    /// Transaction to sign (signer must check that `raw_data_bytes` hash to `txID`)
+    pub transaction: Transaction,
+}
+
+/// Prehash signing request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignPrehashRequest {
+    /// Hex hash to sign
+    pub prehash: String,
+}
+
+/// Signing response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignatureResponse {
+    /// 65-byte signature `r || s || v`
+    pub signature: TronSignature,
+}
+
+/// Error response
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ErrorResponse {
+    /// Error message
+    pub error: String,
+}
+
+/// Signer which delegates signing to remote HTTP service
+#[derive(Debug)]
+pub struct RemoteSigner {
+    client: Client,
+    url: Url,
+    auth_token: Option<String>,
+    public_key: OnceLock<VerifyingKey>,
+}
+
+impl RemoteSigner {
+    /// Create remote signer for service at `url`
+    pub fn new(url: impl IntoUrl) -> Result<Self, crate::Error> {
+        let mut url = url.into_url()?;
+        if !url.path().ends_with('/') {
+            url.set_path(&format!("{}/", url.path()));
+        }
+        Ok(Self {
+            client: Client::new(),
+            url,
+            auth_token: None,
+            public_key: OnceLock::new(),
+        })
+    }
+
+    /// Use custom reqwest client
+    pub fn with_client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    /// Send bearer token with every request
+    pub fn with_auth_token(mut self, token: impl Into<String>) -> Self {
+        self.auth_token = Some(token.into());
+        self
+    }
+
+    fn endpoint(&self, method: &str) -> Result<Url, crate::Error> {
+        Ok(self.url.join(method)?)
+    }
+
+    async fn request<R: DeserializeOwned>(
+        &self,
+        request: reqwest::RequestBuilder,
+    ) -> Result<R, crate::Error> {
+        let request = match &self.auth_token {
+            Some(token) => request.bearer_auth(token),
+            None => request,
+        };
+        let resp = request.send().await?;
+        if !resp.status().is_success() {
+            let status = resp.status();
+            let error = resp
+                .json::<ErrorResponse>()
+                .await
+                .map(|e| e.error)
+                .unwrap_or_else(|_| status.to_string());
+            return Err(crate::Error::SignerError(error));
+        }
+        Ok(resp.json().await?)
+    }
+
+    async fn check_signature(
+        &self,
+        prehash: &[u8],
+        signature: &TronSignature,
+    ) -> Result<(Signature, RecoveryId), crate::Error> {
+        let expected = AsyncSigner::public_key(self).await?;
+        let key = recover_public_key(prehash, signature)
+            .map_err(|e| crate::Error::SignerError(format!("{:?}", e)))?;
+        if key != expected {
+            return Err(crate::Error::SignerError(
+                "signature does not match signer public key".to_owned(),
+            ));
+        }
+        let rec_id = RecoveryId::from_byte(signature.recovery_id).expect("Checked by recovery");
+        let signature = Signature::from_scalars(signature.r, signature.s)
+            .map_err(|e| crate::Error::SignerError(e.to_string()))?;
+        Ok((signature, rec_id))
+    }
+}
+
+impl AsyncSigner for RemoteSigner {
+    type Error = crate::Error;
+
+    async fn public_key(&self) -> Result<VerifyingKey, Self::Error> {
+        if let Some(key) = self.public_key.get() {
+            return Ok(*key);
+        }
+        let resp: PublicKeyResponse = self
+            .request(self.client.get(self.endpoint("public_key")?))
+            .await?;
+        let key = hex::decode(&resp.public_key)
+            .ok()
+            .and_then(|bytes| VerifyingKey::from_sec1_bytes(&bytes).ok())
+            .ok_or_else(|| crate::Error::UnknownResponse(resp.public_key))?;
+        Ok(*self.public_key.get_or_init(|| key))
+    }
+
+    async fn sign_prehash(&self, prehash: &[u8]) -> Result<(Signature, RecoveryId), Self::Error> {
+        let resp: SignatureResponse =
+            self.request(self.client.post(self.endpoint("sign_prehash")?).json(
+                &SignPrehashRequest {
+                    prehash: hex::encode(prehash),
+                },
+            ))
+            .await?;
+        self.check_signature(prehash, &resp.signature).await
+    }
+
+    async fn sign_transaction(&self, tx: &mut Transaction) -> Result<(), Self::Error> {
+        let resp: SignatureResponse = self
+            .request(self.client.post(self.endpoint("sign_transaction")?).json(
+                &SignTransactionRequest {
+                    transaction: tx.clone(),
+                },
+            ))
+            .await?;
+        self.check_signature(&tx.tx_id.0, &resp.signature).await?;
+        tx.signature.push(resp.signature);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use axum::{
+        extract::State,
+        http::{HeaderMap, StatusCode},
+        routing::{get, post},
+        Json, Router,
+    };
+    use heliosphere_core::{
+        block::BlockId, contract::TransferContract, transaction::TransactionBuilder,
+    };
+    use heliosphere_signer::{keypair::Keypair, signer::Signer, SignedTransaction};
+
+    use super::*;
+
+    type ServerState = Arc<Keypair>;
+    type Reply<T> = Result<Json<T>, (StatusCode, Json<ErrorResponse>)>;
+
+    fn reject(status: StatusCode, error: &str) -> (StatusCode, Json<ErrorResponse>) {
+        (
+            status,
+            Json(ErrorResponse {
+                error: error.to_owned(),
+            }),
+        )
+    }
+
+    fn authorize(headers: &HeaderMap) -> Result<(), (StatusCode, Json<ErrorResponse>)> {
+        match headers.get("authorization").and_then(|h| h.to_str().ok()) {
+            Some("Bearer secret") => Ok(()),
+            _ => Err(reject(StatusCode::UNAUTHORIZED, "unauthorized")),
+        }
+    }
+
+    async fn public_key(State(keypair): State<ServerState>) -> Json<PublicKeyResponse> {
+        Json(PublicKeyResponse {
+            public_key: hex::encode(keypair.public_key().to_sec1_bytes()),
+        })
+    }
+
+    async fn sign_transaction(
+        State(keypair): State<ServerState>,
+        headers: HeaderMap,
+        Json(req): Json<SignTransactionRequest>,
+    ) -> Reply<SignatureResponse> {
+        authorize(&headers)?;
+        let mut tx = req.transaction;
+        tx.decode_raw_data()
+            .map_err(|_| reject(StatusCode::BAD_REQUEST, "invalid transaction"))?;
+        tx.signature.clear();
+        Signer::sign_transaction(&*keypair, &mut tx).unwrap();
+        Ok(Json(SignatureResponse {
+            signature: tx.signature[0],
+        }))
+    }
+
+    async fn sign_prehash(
+        headers: HeaderMap,
+        Json(req): Json<SignPrehashRequest>,
+    ) -> Reply<SignatureResponse> {
+        authorize(&headers)?;
+        // misbehaving server: signs with another key
+        let other = Keypair::from_hex_key(&hex::encode([7; 32])).unwrap();
+        let (signature, rec_id) =
+            Signer::sign_prehash(&other, &hex::decode(req.prehash).unwrap()).unwrap();
+        let (r, s) = signature.split_bytes();
+        Ok(Json(SignatureResponse {
+            signature: TronSignature::new(r.into(), s.into(), rec_id.to_byte()).unwrap(),
+        }))
+    }
+
+    async fn serve(keypair: Keypair) -> Url {
+        let app = Router::new()
+            .route("/signer/public_key", get(public_key))
+            .route("/signer/sign_transaction", post(sign_transaction))
+            .route("/signer/sign_prehash", post(sign_prehash))
+            .with_state(Arc::new(keypair));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        format!("http://{}/signer", addr).parse().unwrap()
+    }
+
+    fn keypair() -> Keypair {
+        Keypair::from_hex_key("b224f69fe10604d71263971ecd9cdc9f2fc59d0e7ddb6c9df2aa8631423d8cb0")
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_remote_signer() {
+        let url = serve(keypair()).await;
+        let signer = RemoteSigner::new(url.clone())
+            .unwrap()
+            .with_auth_token("secret");
+        assert_eq!(signer.address().await.unwrap(), Signer::address(&keypair()));
+
+        let mut tx = TransactionBuilder::new(TransferContract {
+            owner_address: Signer::address(&keypair()),
+            to_address: Signer::address(&keypair()),
+            amount: 1,
+        })
+        .with_ref_block_id(BlockId([0x01; 32]))
+        .with_timestamp(1)
+        .build()
+        .unwrap();
+        signer.sign_transaction(&mut tx).await.unwrap();
+        assert!(tx.verify_signed_by(&Signer::address(&keypair())).unwrap());
+
+        assert!(matches!(
+            signer.sign_prehash(&tx.tx_id.0).await,
+            Err(crate::Error::SignerError(e)) if e.contains("does not match")
+        ));
+        let unauthorized = RemoteSigner::new(url).unwrap();
+        assert!(matches!(
+            unauthorized.sign_transaction(&mut tx).await,
+            Err(crate::Error::SignerError(e)) if e == "unauthorized"
+        ));
+    }
+}
@@ -0,0 +1,440 @@
+//! TRC20 token client
+//!
+//! Thin typed wrapper over [`RpcClient::query_contract`] and [`RpcClient::trigger_contract`]
+//! for standard TRC20 methods. Token metadata (name, symbol, decimals) is cached by the handle.
+
+use std::sync::OnceLock;
+
+use heliosphere_core::{
+    amount::U256,
+    transaction::{Transaction, TransactionId},
+    Address, Sun, TokenAmount,
+};
+use heliosphere_signer::async_signer::AsyncSigner;
+
+use crate::{MethodCall, RpcClient};
+
+/// ABI encode address as 32 bytes word
+fn encode_address(address: &Address) -> [u8; 32] {
+    let mut word = [0; 32];
+    word[12..].copy_from_slice(&address.as_bytes()[1..]);
+    word
+}
+
+fn invalid_abi(what: &str) -> crate::Error {
+    crate::Error::UnknownResponse(format!("invalid ABI encoded {}", what))
+}
+
+/// ABI decode `uint256`
+fn decode_uint(data: &[u8]) -> Result<U256, crate::Error> {
+    data.get(..32)
+        .map(U256::from_be_slice)
+        .ok_or_else(|| invalid_abi("uint256"))
+}
+
+/// ABI decode `string` (some early tokens return `bytes32` instead)
+fn decode_string(data: &[u8]) -> Result<String, crate::Error> {
+    let bytes = if data.len() == 32 {
+        let len = data.iter().position(|b| *b == 0).unwrap_or(32);
+        &data[..len]
+    } else {
+        let offset: usize = decode_uint(data)?
+            .try_into()
+            .map_err(|_| invalid_abi("string"))?;
+        let tail = data.get(offset..).ok_or_else(|| invalid_abi("string"))?;
+        let len: usize = decode_uint(tail)?
+            .try_into()
+            .map_err(|_| invalid_abi("string"))?;
+        tail.get(32..)
+            .and_then(|s| s.get(..len))
+            .ok_or_else(|| invalid_abi("string"))?
+    };
+    String::from_utf8(bytes.to_vec()).map_err(|_| invalid_abi("string"))
+}
+
+impl RpcClient {
+    /// Get TRC20 token handle
+    pub fn trc20(&self, contract: Address) -> Trc20 {
+        Trc20::new(self.clone(), contract)
+    }
+}
+
+/// TRC20 token handle
+#[derive(Clone)]
+pub struct Trc20 {
+    client: RpcClient,
+    contract: Address,
+    name: OnceLock<String>,
+    symbol: OnceLock<String>,
+    decimals: OnceLock<u8>,
+}
+
+impl Trc20 {
+    /// Create new handle
+    pub fn new(client: RpcClient, contract: Address) -> Self {
+        Self {
+            client,
+            contract,
+            name: OnceLock::new(),
+            symbol: OnceLock::new(),
+            decimals: OnceLock::new(),
+        }
+    }
+
+    /// Token contract address
+    pub fn contract(&self) -> &Address {
+        &self.contract
+    }
+
+    async fn query(&self, selector: &str, parameter: &[u8]) -> Result<Vec<u8>, crate::Error> {
+        self.client
+            .query_contract(&MethodCall {
+                caller: &self.contract,
+                contract: &self.contract,
+                selector,
+                parameter,
+            })
+            .await?
+            .constant_result(0)
+    }
+
+    async fn amount(&self, selector: &str, parameter: &[u8]) -> Result<TokenAmount, crate::Error> {
+        let value = decode_uint(&self.query(selector, parameter).await?)?;
+        Ok(TokenAmount::new(value, self.decimals().await?))
+    }
+
+    /// Token name (cached)
+    pub async fn name(&self) -> Result<String, crate::Error> {
+        if let Some(name) = self.name.get() {
+            return Ok(name.clone());
+        }
+        let name = decode_string(&self.query("name()", &[]).await?)?;
+        Ok(self.name.get_or_init(|| name).clone())
+    }
+
+    /// Token symbol (cached)
+    pub async fn symbol(&self) -> Result<String, crate::Error> {
+        if let Some(symbol) = self.symbol.get() {
+            return Ok(symbol.clone());
+        }
+        let symbol = decode_string(&self.query("symbol()", &[]).await?)?;
+        Ok(self.symbol.get_or_init(|| symbol).clone())
+    }
+
+    /// Token decimals (cached)
+    pub async fn decimals(&self) -> Result<u8, crate::Error> {
+        if let Some(decimals) = self.decimals.get() {
+            return Ok(*decimals);
+        }
+        let decimals = decode_uint(&self.query("decimals()", &[]).await?)?
+            .try_into()
+            .map_err(|_| invalid_abi("uint8"))?;
+        Ok(*self.decimals.get_or_init(|| decimals))
+    }
+
+    /// Total token supply
+    pub async fn total_supply(&self) -> Result<TokenAmount, crate::Error> {
+        self.amount("totalSupply()", &[]).await
+    }
+
+    /// Token balance of account
+    pub async fn balance_of(&self, account: &Address) -> Result<TokenAmount, crate::Error> {
+        self.amount("balanceOf(address)", &encode_address(account))
+            .await
+    }
+
+    /// Amount `spender` is allowed to transfer from `owner`
+    pub async fn allowance(
+        &self,
+        owner: &Address,
+        spender: &Address,
+    ) -> Result<TokenAmount, crate::Error> {
+        let parameter = [encode_address(owner), encode_address(spender)].concat();
+        self.amount("allowance(address,address)", &parameter).await
+    }
+
+    /// ABI encode amount, checking it has token decimals
+    async fn encode_amount(&self, amount: &TokenAmount) -> Result<[u8; 32], crate::Error> {
+        let decimals = self.decimals().await?;
+        if amount.decimals() != decimals {
+            return Err(crate::Error::TokenDecimalsMismatch(
+                decimals,
+                amount.decimals(),
+            ));
+        }
+        Ok(amount.value().to_be_bytes())
+    }
+
+    async fn trigger(
+        &self,
+        caller: &Address,
+        selector: &str,
+        parameter: &[u8],
+        fee_limit: Option<Sun>,
+    ) -> Result<Transaction, crate::Error> {
+        let method_call = MethodCall {
+            caller,
+            contract: &self.contract,
+            selector,
+            parameter,
+        };
+        self.client
+            .trigger_contract(&method_call, Sun::ZERO, fee_limit)
+            .await
+    }
+
+    async fn address_of(signer: &impl AsyncSigner) -> Result<Address, crate::Error> {
+        signer
+            .address()
+            .await
+            .map_err(|e| crate::Error::SignerError(format!("{:?}", e)))
+    }
+
+    /** Create `transfer` transaction
+     ** from - Token holder
+     ** to - Receiver
+     ** amount - Amount of tokens (with token decimals)
+     ** fee_limit - Maximum TRX consumption (estimated if not set)
+     */
+    pub async fn transfer(
+        &self,
+        from: &Address,
+        to: &Address,
+        amount: &TokenAmount,
+        fee_limit: Option<Sun>,
+    ) -> Result<Transaction, crate::Error> {
+        let parameter = [encode_address(to), self.encode_amount(amount).await?].concat();
+        self.trigger(from, "transfer(address,uint256)", &parameter, fee_limit)
+            .await
+    }
+
+    /** Create `approve` transaction
+     ** owner - Token holder
+     ** spender - Account allowed to transfer tokens of `owner`
+     ** amount - Allowance (with token decimals)
+     ** fee_limit - Maximum TRX consumption (estimated if not set)
+     */
+    pub async fn approve(
+        &self,
+        owner: &Address,
+        spender: &Address,
+        amount: &TokenAmount,
+        fee_limit: Option<Sun>,
+    ) -> Result<Transaction, crate::Error> {
+        let parameter = [encode_address(spender), self.encode_amount(amount).await?].concat();
+        self.trigger(owner, "approve(address,uint256)", &parameter, fee_limit)
+            .await
+    }
+
+    /** Create `transferFrom` transaction
+     ** spender - Account with allowance, sender of the transaction
+     ** from - Token holder
+     ** to - Receiver
+     ** amount - Amount of tokens (with token decimals)
+     ** fee_limit - Maximum TRX consumption (estimated if not set)
+     */
+    pub async fn transfer_from(
+        &self,
+        spender: &Address,
+        from: &Address,
+        to: &Address,
+        amount: &TokenAmount,
+        fee_limit: Option<Sun>,
+    ) -> Result<Transaction, crate::Error> {
+        let parameter = [
+            encode_address(from),
+            encode_address(to),
+            self.encode_amount(amount).await?,
+        ]
+        .concat();
+        self.trigger(
+            spender,
+            "transferFrom(address,address,uint256)",
+            &parameter,
+            fee_limit,
+        )
+        .await
+    }
+
+    /// Transfer tokens from signer address, sign and broadcast transaction
+    pub async fn send_transfer(
+        &self,
+        signer: &impl AsyncSigner,
+        to: &Address,
+        amount: &TokenAmount,
+        fee_limit: Option<Sun>,
+    ) -> Result<TransactionId, crate::Error> {
+        let from = Self::address_of(signer).await?;
+        let mut tx = self.transfer(&from, to, amount, fee_limit).await?;
+        self.client.sign_and_broadcast(&mut tx, signer).await
+    }
+
+    /// Approve spender on behalf of signer, sign and broadcast transaction
+    pub async fn send_approve(
+        &self,
+        signer: &impl AsyncSigner,
+        spender: &Address,
+        amount: &TokenAmount,
+        fee_limit: Option<Sun>,
+    ) -> Result<TransactionId, crate::Error> {
+        let owner = Self::address_of(signer).await?;
+        let mut tx = self.approve(&owner, spender, amount, fee_limit).await?;
+        self.client.sign_and_broadcast(&mut tx, signer).await
+    }
+
+    /// Transfer tokens of `from` using signer's allowance, sign and broadcast transaction
+    pub async fn send_transfer_from(
+        &self,
+        signer: &impl AsyncSigner,
+        from: &Address,
+        to: &Address,
+        amount: &TokenAmount,
+        fee_limit: Option<Sun>,
+    ) -> Result<TransactionId, crate::Error> {
+        let spender = Self::address_of(signer).await?;
+        let mut tx = self
+            .transfer_from(&spender, from, to, amount, fee_limit)
+            .await?;
+        self.client.sign_and_broadcast(&mut tx, signer).await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    use axum::{extract::State, routing::post, Json, Router};
+    use heliosphere_core::{
+        block::BlockId,
+        contract::{ContractKind, TriggerSmartContract},
+        transaction::TransactionBuilder,
+        util::method_selector,
+    };
+    use serde_json::{json, Value};
+
+    use super::*;
+
+    fn abi_string(s: &str) -> Vec<u8> {
+        let mut data = U256::from(32).to_be_bytes::<32>().to_vec();
+        data.extend_from_slice(&U256::from(s.len()).to_be_bytes::<32>());
+        data.extend_from_slice(s.as_bytes());
+        data.resize(64 + s.len().div_ceil(32) * 32, 0);
+        data
+    }
+
+    async fn constant(
+        State(calls): State<Arc<AtomicUsize>>,
+        Json(req): Json<Value>,
+    ) -> Json<Value> {
+        let result = match req["function_selector"].as_str().unwrap() {
+            "name()" => abi_string("Tether USD"),
+            // bytes32 symbol
+            "symbol()" => {
+                let mut word = [0; 32];
+                word[..4].copy_from_slice(b"USDT");
+                word.to_vec()
+            }
+            "decimals()" => {
+                calls.fetch_add(1, Ordering::Relaxed);
+                U256::from(6).to_be_bytes::<32>().to_vec()
+            }
+            "balanceOf(address)" => U256::from(1_500_000).to_be_bytes::<32>().to_vec(),
+            selector => panic!("unexpected call {}", selector),
+        };
+        Json(json!({
+            "result": {"result": true},
+            "constant_result": [hex::encode(result)],
+            "energy_used": 0
+        }))
+    }
+
+    async fn trigger(Json(req): Json<Value>) -> Json<Value> {
+        let address = |key: &str| req[key].as_str().unwrap().parse().unwrap();
+        let data = [
+            &method_selector(req["function_selector"].as_str().unwrap())[..],
+            &hex::decode(req["parameter"].as_str().unwrap()).unwrap(),
+        ]
+        .concat();
+        let tx = TransactionBuilder::new(TriggerSmartContract {
+            owner_address: address("owner_address"),
+            contract_address: address("contract_address"),
+            call_value: 0,
+            data,
+            call_token_value: 0,
+            token_id: 0,
+        })
+        .with_ref_block_id(BlockId([0x01; 32]))
+        .with_timestamp(1)
+        .with_fee_limit(Sun::new(req["fee_limit"].as_u64().unwrap()))
+        .build()
+        .unwrap();
+        Json(json!({ "transaction": tx }))
+    }
+
+    async fn serve(calls: Arc<AtomicUsize>) -> RpcClient {
+        let app = Router::new()
+            .route("/wallet/triggerconstantcontract", post(constant))
+            .route("/wallet/triggersmartcontract", post(trigger))
+            .with_state(calls);
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        RpcClient::new(format!("http://{}", addr).as_str()).unwrap()
+    }
+
+    #[test]
+    fn test_abi() {
+        assert_eq!(decode_string(&abi_string("")).unwrap(), "");
+        let long = "a".repeat(40);
+        assert_eq!(decode_string(&abi_string(&long)).unwrap(), long);
+        assert!(decode_string(&abi_string(&long)[..80]).is_err());
+        assert!(decode_uint(&[0; 31]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_trc20() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let client = serve(calls.clone()).await;
+        let usdt: Address = "TR7NHqjeKQxGTCi8q8ZY4pL8otSzgjLj6t".parse().unwrap();
+        let holder: Address = "TNPeeaaFB7K9cmo4uQpcU32zGK8G1NYqeL".parse().unwrap();
+        let token = client.trc20(usdt);
+
+        assert_eq!(token.name().await.unwrap(), "Tether USD");
+        assert_eq!(token.symbol().await.unwrap(), "USDT");
+        let balance = token.balance_of(&holder).await.unwrap();
+        assert_eq!(balance.to_string(), "1.5");
+        assert_eq!(token.decimals().await.unwrap(), 6);
+        assert_eq!(calls.load(Ordering::Relaxed), 1, "decimals are cached");
+
+        let amount = TokenAmount::parse("0.25", 6).unwrap();
+        let tx = token
+            .transfer(&holder, &usdt, &amount, Some(Sun::new(10_000_000)))
+            .await
+            .unwrap();
+        let raw_data = tx.decode_raw_data().unwrap();
+        let ContractKind::TriggerSmartContract(contract) = &raw_data.contract[0].kind else {
+            panic!("unexpected contract");
+        };
+        assert_eq!(contract.owner_address, holder);
+        assert_eq!(
+            contract.data[..4],
+            method_selector("transfer(address,uint256)")
+        );
+        assert_eq!(contract.data[4..36], encode_address(&usdt));
+        assert_eq!(
+            U256::from_be_slice(&contract.data[36..]),
+            U256::from(250_000)
+        );
+        assert_eq!(raw_data.fee_limit, 10_000_000);
+
+        assert!(matches!(
+            token
+                .approve(&holder, &usdt, &TokenAmount::zero(18), Some(Sun::new(1)))
+                .await,
+            Err(crate::Error::TokenDecimalsMismatch(6, 18))
+        ));
+    }
+}
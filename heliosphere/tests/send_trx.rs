@@ -1,5 +1,5 @@
 use heliosphere::RpcClient;
-use heliosphere_core::Address;
+use heliosphere_core::{Address, Sun};
 use heliosphere_signer::{keypair::Keypair, signer::Signer};
 
 #[tokio::test]
@@ -14,7 +14,7 @@ async fn test_send_trx() {
     let client = RpcClient::new(api).unwrap();
     let from = keypair.address();
     let to: Address = "TB9n2jzcWoqta1xX2Mv8P3y9tyUNsGTFsQ".parse().unwrap();
-    let amount = 1;
+    let amount = Sun::new(1);
     let old_balance = client.get_account_balance(&from).await.unwrap();
     let mut tx = client.trx_transfer(&from, &to, amount).await.unwrap();
     keypair.sign_transaction(&mut tx).unwrap();
@@ -24,5 +24,5 @@ async fn test_send_trx() {
     let info = client.await_confirmation(txid).await.unwrap();
     println!("{:?}", info);
     let new_balance = client.get_account_balance(&from).await.unwrap();
-    assert!(old_balance >= new_balance + amount); // including TRX burn
+    assert!(old_balance >= new_balance.checked_add(amount).unwrap()); // including TRX burn
 }